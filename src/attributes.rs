@@ -0,0 +1,2378 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use std::str::FromStr;
+
+use crate::s57::{AttributeValue, MultiGeometry, Position, S57Attribute, S57Type, S57};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum VerticalDatum {
+    Unknown = 0,
+    MeanLowWaterSprings = 1,
+    MeanLowerLowWaterSprings = 2,
+    MeanSeaLevel = 3,
+    LowestLowWater = 4,
+    MeanLowWater = 5,
+    LowestLowWaterSprings = 6,
+    ApproximateMeanLowWaterSprings = 7,
+    IndianSpringLowWater = 8,
+    LowWaterSprings = 9,
+    ApproximateLowestAstronomicalTide = 10,
+    NearlyLowestLowWater = 11,
+    MeanLowerLowWater = 12,
+    LowWater = 13,
+    ApproximateMeanLowWater = 14,
+    ApproximateMeanLowerLowWater = 15,
+    MeanHighWater = 16,
+    MeanHighWaterSprings = 17,
+    HighWater = 18,
+    ApproximateMeanSeaLevel = 19,
+    HighWaterSprings = 20,
+    MeanHigherHighWater = 21,
+    EquinoctialSpringLowWater = 22,
+    LowestAstronomicalTide = 23,
+    LocalDatum = 24,
+    InternationalGreatLakesDatum1985 = 25,
+    MeanWaterLevel = 26,
+    LowerLowWaterLargeTide = 27,
+    HigherHighWaterLargeTide = 28,
+    NearlyHighestHighWater = 29,
+    HighestAstronomicalTide = 30,
+}
+
+#[allow(dead_code)]
+impl VerticalDatum {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => VerticalDatum::MeanLowWaterSprings,
+            2 => VerticalDatum::MeanLowerLowWaterSprings,
+            3 => VerticalDatum::MeanSeaLevel,
+            4 => VerticalDatum::LowestLowWater,
+            5 => VerticalDatum::MeanLowWater,
+            6 => VerticalDatum::LowestLowWaterSprings,
+            7 => VerticalDatum::ApproximateMeanLowWaterSprings,
+            8 => VerticalDatum::IndianSpringLowWater,
+            9 => VerticalDatum::LowWaterSprings,
+            10 => VerticalDatum::ApproximateLowestAstronomicalTide,
+            11 => VerticalDatum::NearlyLowestLowWater,
+            12 => VerticalDatum::MeanLowerLowWater,
+            13 => VerticalDatum::LowWater,
+            14 => VerticalDatum::ApproximateMeanLowWater,
+            15 => VerticalDatum::ApproximateMeanLowerLowWater,
+            16 => VerticalDatum::MeanHighWater,
+            17 => VerticalDatum::MeanHighWaterSprings,
+            18 => VerticalDatum::HighWater,
+            19 => VerticalDatum::ApproximateMeanSeaLevel,
+            20 => VerticalDatum::HighWaterSprings,
+            21 => VerticalDatum::MeanHigherHighWater,
+            22 => VerticalDatum::EquinoctialSpringLowWater,
+            23 => VerticalDatum::LowestAstronomicalTide,
+            24 => VerticalDatum::LocalDatum,
+            25 => VerticalDatum::InternationalGreatLakesDatum1985,
+            26 => VerticalDatum::MeanWaterLevel,
+            27 => VerticalDatum::LowerLowWaterLargeTide,
+            28 => VerticalDatum::HigherHighWaterLargeTide,
+            29 => VerticalDatum::NearlyHighestHighWater,
+            30 => VerticalDatum::HighestAstronomicalTide,
+            _ => VerticalDatum::Unknown,
+        }
+    }
+}
+
+/// Abbreviation and full name of each VERDAT code
+const VERTICAL_DATUM_NAMES: [(u32, &str, &str); 30] = [
+    (1, "MLWS", "mean low water springs"),
+    (2, "MLLWS", "mean lower low water springs"),
+    (3, "MSL", "mean sea level"),
+    (4, "LLW", "lowest low water"),
+    (5, "MLW", "mean low water"),
+    (6, "LLWS", "lowest low water springs"),
+    (7, "AMLWS", "approximate mean low water springs"),
+    (8, "ISLW", "indian spring low water"),
+    (9, "LWS", "low water springs"),
+    (10, "ALAT", "approximate lowest astronomical tide"),
+    (11, "NLLW", "nearly lowest low water"),
+    (12, "MLLW", "mean lower low water"),
+    (13, "LW", "low water"),
+    (14, "AMLW", "approximate mean low water"),
+    (15, "AMLLW", "approximate mean lower low water"),
+    (16, "MHW", "mean high water"),
+    (17, "MHWS", "mean high water springs"),
+    (18, "HW", "high water"),
+    (19, "AMSL", "approximate mean sea level"),
+    (20, "HWS", "high water springs"),
+    (21, "MHHW", "mean higher high water"),
+    (22, "ESLW", "equinoctial spring low water"),
+    (23, "LAT", "lowest astronomical tide"),
+    (24, "LOCAL", "local datum"),
+    (25, "IGLD1985", "international great lakes datum 1985"),
+    (26, "MWL", "mean water level"),
+    (27, "LLWLT", "lower low water large tide"),
+    (28, "HHWLT", "higher high water large tide"),
+    (29, "NHHW", "nearly highest high water"),
+    (30, "HAT", "highest astronomical tide"),
+];
+
+/// Datum the soundings of a cell refer to, same codes as VERDAT
+#[allow(dead_code)]
+pub type SoundingDatum = VerticalDatum;
+
+impl FromStr for VerticalDatum {
+    type Err = ();
+
+    /// Parses abbreviations ("MLLW") and full names ("Lowest Astronomical Tide")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s
+            .trim_end_matches('\0')
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let abbreviation = name.replace(' ', "").to_uppercase();
+
+        VERTICAL_DATUM_NAMES
+            .iter()
+            .find(|(_, short, long)| *short == abbreviation || *long == name)
+            .map(|(code, _, _)| VerticalDatum::from_code(*code))
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum HorizontalDatum {
+    Unknown = 0,
+    Wgs72 = 1,
+    Wgs84 = 2,
+    European1950 = 3,
+    Potsdam = 4,
+}
+
+#[allow(dead_code)]
+impl HorizontalDatum {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => HorizontalDatum::Wgs72,
+            2 => HorizontalDatum::Wgs84,
+            3 => HorizontalDatum::European1950,
+            4 => HorizontalDatum::Potsdam,
+            _ => HorizontalDatum::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    pub fn vertical_datum(&self) -> Option<VerticalDatum> {
+        self.attribute(S57Attribute::VERDAT)
+            .and_then(|value| value.as_u32())
+            .map(VerticalDatum::from_code)
+    }
+
+    pub fn horizontal_datum(&self) -> Option<HorizontalDatum> {
+        self.attribute(S57Attribute::HORDAT)
+            .and_then(|value| value.as_u32())
+            .map(HorizontalDatum::from_code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum DistanceMarkCategory {
+    Unknown = 0,
+    NotPhysicallyInstalled = 1,
+    VisibleMarkPole = 2,
+    VisibleMarkBoard = 3,
+    VisibleMarkUnknownShape = 4,
+}
+
+#[allow(dead_code)]
+impl DistanceMarkCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => DistanceMarkCategory::NotPhysicallyInstalled,
+            2 => DistanceMarkCategory::VisibleMarkPole,
+            3 => DistanceMarkCategory::VisibleMarkBoard,
+            4 => DistanceMarkCategory::VisibleMarkUnknownShape,
+            _ => DistanceMarkCategory::Unknown,
+        }
+    }
+}
+
+/// Parses a distance label like "km 12,5" or "3.5 NM" into metres.
+/// Waterway distances without a unit are kilometres.
+fn parse_distance(text: &str) -> Option<f64> {
+    let text = text.to_lowercase();
+
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let number = &text[start..];
+    let end = number
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(number.len());
+    let value: f64 = number[..end].replace(',', ".").parse().ok()?;
+
+    let factor = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .find_map(|word| match word {
+            "km" => Some(1000.0),
+            "nm" | "nmi" => Some(1852.0),
+            "mi" | "mile" | "miles" => Some(1609.344),
+            "ft" => Some(0.3048),
+            "m" => Some(1.0),
+            _ => None,
+        })
+        .unwrap_or(1000.0);
+
+    Some(value * factor)
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// Distance of a DISMAR feature in metres, read from its name or information text
+    pub fn distance_mark(&self) -> Option<f64> {
+        if self.s57_type() != S57Type::DISMAR {
+            return None;
+        }
+
+        [S57Attribute::OBJNAM, S57Attribute::INFORM]
+            .into_iter()
+            .filter_map(|attribute| self.attribute(attribute))
+            .filter_map(|value| value.as_str())
+            .find_map(parse_distance)
+    }
+
+    pub fn distance_mark_category(&self) -> Option<DistanceMarkCategory> {
+        self.attribute(S57Attribute::CATDIS)
+            .and_then(|value| value.as_u32())
+            .map(DistanceMarkCategory::from_code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum RadioStationCategory {
+    Unknown = 0,
+    CircularRadiobeacon = 1,
+    DirectionalRadiobeacon = 2,
+    RotatingPatternRadiobeacon = 3,
+    ConsolBeacon = 4,
+    RadioDirectionFindingStation = 5,
+    CoastRadioStationQtg = 6,
+    AeronauticalRadiobeacon = 7,
+    Decca = 8,
+    LoranC = 9,
+    DifferentialGps = 10,
+    Toran = 11,
+    Omega = 12,
+    Syledis = 13,
+    Chaika = 14,
+    RadioTelephoneStation = 15,
+    AisBaseStation = 16,
+}
+
+#[allow(dead_code)]
+impl RadioStationCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => RadioStationCategory::CircularRadiobeacon,
+            2 => RadioStationCategory::DirectionalRadiobeacon,
+            3 => RadioStationCategory::RotatingPatternRadiobeacon,
+            4 => RadioStationCategory::ConsolBeacon,
+            5 => RadioStationCategory::RadioDirectionFindingStation,
+            6 => RadioStationCategory::CoastRadioStationQtg,
+            7 => RadioStationCategory::AeronauticalRadiobeacon,
+            8 => RadioStationCategory::Decca,
+            9 => RadioStationCategory::LoranC,
+            10 => RadioStationCategory::DifferentialGps,
+            11 => RadioStationCategory::Toran,
+            12 => RadioStationCategory::Omega,
+            13 => RadioStationCategory::Syledis,
+            14 => RadioStationCategory::Chaika,
+            15 => RadioStationCategory::RadioTelephoneStation,
+            16 => RadioStationCategory::AisBaseStation,
+            _ => RadioStationCategory::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadioStation {
+    pub categories: Vec<RadioStationCategory>,
+    /// Estimated range of transmission in nautical miles (ESTRNG)
+    pub estimated_range: Option<f64>,
+    /// Nominal range in nautical miles (VALNMR)
+    pub nominal_range: Option<f64>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    pub fn radio_station(&self) -> Option<RadioStation> {
+        if self.s57_type() != S57Type::RDOSTA {
+            return None;
+        }
+
+        Some(RadioStation {
+            categories: self
+                .attribute(S57Attribute::CATROS)
+                .map(|value| value.as_list())
+                .unwrap_or_default()
+                .into_iter()
+                .map(RadioStationCategory::from_code)
+                .collect(),
+            estimated_range: self
+                .attribute(S57Attribute::ESTRNG)
+                .and_then(|value| value.as_f64()),
+            nominal_range: self
+                .attribute(S57Attribute::VALNMR)
+                .and_then(|value| value.as_f64()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum HorizontalJustification {
+    Unknown = 0,
+    Centre = 1,
+    Right = 2,
+    Left = 3,
+}
+
+#[allow(dead_code)]
+impl HorizontalJustification {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => HorizontalJustification::Centre,
+            2 => HorizontalJustification::Right,
+            3 => HorizontalJustification::Left,
+            _ => HorizontalJustification::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum VerticalJustification {
+    Unknown = 0,
+    Bottom = 1,
+    Centre = 2,
+    Top = 3,
+}
+
+#[allow(dead_code)]
+impl VerticalJustification {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => VerticalJustification::Bottom,
+            2 => VerticalJustification::Centre,
+            3 => VerticalJustification::Top,
+            _ => VerticalJustification::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartText {
+    pub text: String,
+    pub position: Position,
+    pub horizontal_justification: Option<HorizontalJustification>,
+    pub vertical_justification: Option<VerticalJustification>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// Cartographic text of a TEXTS feature
+    pub fn text(&self) -> Option<ChartText> {
+        if self.s57_type() != S57Type::TEXTS {
+            return None;
+        }
+
+        Some(ChartText {
+            text: self
+                .attribute(S57Attribute::TXSTR)?
+                .as_str()?
+                .trim_end_matches('\0')
+                .to_string(),
+            position: *self.point_geometry()?,
+            horizontal_justification: self
+                .attribute(S57Attribute::JUSTH)
+                .and_then(|value| value.as_u32())
+                .map(HorizontalJustification::from_code),
+            vertical_justification: self
+                .attribute(S57Attribute::JUSTV)
+                .and_then(|value| value.as_u32())
+                .map(VerticalJustification::from_code),
+        })
+    }
+}
+
+/// Colour of the COLOUR attribute, named as in the S-52 presentation library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(dead_code)]
+pub enum S52Colour {
+    Unknown = 0,
+    White = 1,
+    Black = 2,
+    Red = 3,
+    Green = 4,
+    Blue = 5,
+    Yellow = 6,
+    Grey = 7,
+    Brown = 8,
+    Amber = 9,
+    Violet = 10,
+    Orange = 11,
+    Magenta = 12,
+    Pink = 13,
+}
+
+#[allow(dead_code)]
+impl S52Colour {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => S52Colour::White,
+            2 => S52Colour::Black,
+            3 => S52Colour::Red,
+            4 => S52Colour::Green,
+            5 => S52Colour::Blue,
+            6 => S52Colour::Yellow,
+            7 => S52Colour::Grey,
+            8 => S52Colour::Brown,
+            9 => S52Colour::Amber,
+            10 => S52Colour::Violet,
+            11 => S52Colour::Orange,
+            12 => S52Colour::Magenta,
+            13 => S52Colour::Pink,
+            _ => S52Colour::Unknown,
+        }
+    }
+}
+
+/// Buoyage system of a region (MARSYS)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum BuoyageSystem {
+    Unknown = 0,
+    IalaA = 1,
+    IalaB = 2,
+    NoSystem = 9,
+    Other = 10,
+}
+
+#[allow(dead_code)]
+impl BuoyageSystem {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => BuoyageSystem::IalaA,
+            2 => BuoyageSystem::IalaB,
+            9 => BuoyageSystem::NoSystem,
+            10 => BuoyageSystem::Other,
+            _ => BuoyageSystem::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum Side {
+    Port,
+    Starboard,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LateralSide {
+    /// Side the mark is left on when proceeding in the conventional direction of buoyage
+    pub side: Side,
+    /// Main colour of the mark
+    pub colour: S52Colour,
+    /// Modified lateral mark indicating a preferred channel
+    pub preferred_channel: bool,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// Decoded COLOUR list, in the order given on the feature
+    pub fn colours(&self) -> Vec<S52Colour> {
+        self.attribute(S57Attribute::COLOUR)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(S52Colour::from_code)
+            .collect()
+    }
+
+    pub fn buoyage_system(&self) -> Option<BuoyageSystem> {
+        self.attribute(S57Attribute::MARSYS)
+            .and_then(|value| value.as_u32())
+            .map(BuoyageSystem::from_code)
+    }
+
+    /// Resolves CATLAM of a BOYLAT/BCNLAT feature for the given buoyage system
+    pub fn lateral_mark_side(&self, system: BuoyageSystem) -> Option<LateralSide> {
+        if self.s57_type() != S57Type::BOYLAT && self.s57_type() != S57Type::BCNLAT {
+            return None;
+        }
+
+        let (side, preferred_channel) = match self.attribute(S57Attribute::CATLAM)?.as_u32()? {
+            1 => (Side::Port, false),
+            2 => (Side::Starboard, false),
+            3 => (Side::Port, true),
+            4 => (Side::Starboard, true),
+            _ => return None,
+        };
+
+        let colour = match (system, side) {
+            (BuoyageSystem::IalaA, Side::Port) | (BuoyageSystem::IalaB, Side::Starboard) => {
+                S52Colour::Red
+            }
+            (BuoyageSystem::IalaA, Side::Starboard) | (BuoyageSystem::IalaB, Side::Port) => {
+                S52Colour::Green
+            }
+            _ => return None,
+        };
+
+        Some(LateralSide {
+            side,
+            colour,
+            preferred_channel,
+        })
+    }
+
+    /// Colours a mark should be drawn with, top to bottom. Lateral marks take
+    /// their colours from CATLAM under the feature's own MARSYS (falling back
+    /// to `system`), cardinal marks from CATCAM, everything else from COLOUR.
+    /// Empty if STATUS marks the feature as not in use.
+    pub fn effective_colours(&self, system: BuoyageSystem) -> Vec<S52Colour> {
+        let not_in_use = self
+            .attribute(S57Attribute::STATUS)
+            .is_some_and(|value| value.as_list().contains(&4));
+        if not_in_use {
+            return Vec::new();
+        }
+
+        let system = self
+            .buoyage_system()
+            .filter(|system| matches!(system, BuoyageSystem::IalaA | BuoyageSystem::IalaB))
+            .unwrap_or(system);
+
+        if let Some(lateral) = self.lateral_mark_side(system) {
+            if !lateral.preferred_channel {
+                return vec![lateral.colour];
+            }
+
+            let band = match lateral.colour {
+                S52Colour::Red => S52Colour::Green,
+                _ => S52Colour::Red,
+            };
+            return vec![lateral.colour, band, lateral.colour];
+        }
+
+        if matches!(self.s57_type(), S57Type::BOYCAR | S57Type::BCNCAR) {
+            let cardinal = self
+                .attribute(S57Attribute::CATCAM)
+                .and_then(|value| value.as_u32());
+
+            match cardinal {
+                Some(1) => return vec![S52Colour::Black, S52Colour::Yellow],
+                Some(2) => return vec![S52Colour::Black, S52Colour::Yellow, S52Colour::Black],
+                Some(3) => return vec![S52Colour::Yellow, S52Colour::Black],
+                Some(4) => return vec![S52Colour::Yellow, S52Colour::Black, S52Colour::Yellow],
+                _ => {}
+            }
+        }
+
+        self.colours()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum TrafficFlow {
+    Unknown = 0,
+    Inbound = 1,
+    Outbound = 2,
+    OneWay = 3,
+    TwoWay = 4,
+}
+
+#[allow(dead_code)]
+impl TrafficFlow {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => TrafficFlow::Inbound,
+            2 => TrafficFlow::Outbound,
+            3 => TrafficFlow::OneWay,
+            4 => TrafficFlow::TwoWay,
+            _ => TrafficFlow::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairwayInfo {
+    /// Orientation in degrees true
+    pub orientation: Option<f64>,
+    pub traffic_flow: Option<TrafficFlow>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// ORIENT in degrees true
+    pub fn orientation(&self) -> Option<f64> {
+        self.attribute(S57Attribute::ORIENT)
+            .and_then(|value| value.as_f64())
+    }
+
+    pub fn traffic_flow(&self) -> Option<TrafficFlow> {
+        self.attribute(S57Attribute::TRAFIC)
+            .and_then(|value| value.as_u32())
+            .map(TrafficFlow::from_code)
+    }
+
+    pub fn fairway(&self) -> Option<FairwayInfo> {
+        if self.s57_type() != S57Type::FAIRWY {
+            return None;
+        }
+
+        Some(FairwayInfo {
+            orientation: self.orientation(),
+            traffic_flow: self.traffic_flow(),
+        })
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecommendedTrack {
+    /// Charted bearing in degrees true
+    pub bearing: f64,
+    /// Bearing of the reciprocal track, for two-way tracks
+    pub reciprocal: Option<f64>,
+    pub traffic_flow: Option<TrafficFlow>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// ORIENT and TRAFIC of a RECTRC feature. ORIENT may hold both
+    /// bearings of a reciprocal track ("045,225"), otherwise the reciprocal
+    /// of a two-way track is derived from the charted bearing.
+    pub fn recommended_track(&self) -> Option<RecommendedTrack> {
+        if self.s57_type() != S57Type::RECTRC {
+            return None;
+        }
+
+        let bearings: Vec<f64> = match self.attribute(S57Attribute::ORIENT)? {
+            AttributeValue::String(value) => value
+                .trim_end_matches('\0')
+                .split(',')
+                .filter_map(|bearing| bearing.trim().parse().ok())
+                .collect(),
+            value => value.as_f64().into_iter().collect(),
+        };
+
+        let bearing = *bearings.first()?;
+        let traffic_flow = self.traffic_flow();
+
+        let reciprocal = bearings.get(1).copied().or_else(|| {
+            (traffic_flow == Some(TrafficFlow::TwoWay)).then(|| (bearing + 180.0) % 360.0)
+        });
+
+        Some(RecommendedTrack {
+            bearing,
+            reciprocal,
+            traffic_flow,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum WaterLevel {
+    Unknown = 0,
+    PartlySubmergedAtHighWater = 1,
+    AlwaysDry = 2,
+    AlwaysUnderWater = 3,
+    CoversAndUncovers = 4,
+    Awash = 5,
+    SubjectToFlooding = 6,
+    Floating = 7,
+}
+
+#[allow(dead_code)]
+impl WaterLevel {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => WaterLevel::PartlySubmergedAtHighWater,
+            2 => WaterLevel::AlwaysDry,
+            3 => WaterLevel::AlwaysUnderWater,
+            4 => WaterLevel::CoversAndUncovers,
+            5 => WaterLevel::Awash,
+            6 => WaterLevel::SubjectToFlooding,
+            7 => WaterLevel::Floating,
+            _ => WaterLevel::Unknown,
+        }
+    }
+
+    /// Whether the feature's visibility depends on the height of tide
+    pub fn is_tidal(&self) -> bool {
+        matches!(
+            self,
+            WaterLevel::PartlySubmergedAtHighWater
+                | WaterLevel::CoversAndUncovers
+                | WaterLevel::Awash
+        )
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    pub fn water_level(&self) -> Option<WaterLevel> {
+        self.attribute(S57Attribute::WATLEV)
+            .and_then(|value| value.as_u32())
+            .map(WaterLevel::from_code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum DepthUnit {
+    Unknown = 0,
+    Metres = 1,
+    FathomsAndFeet = 2,
+    Feet = 3,
+    FathomsAndFractions = 4,
+}
+
+#[allow(dead_code)]
+impl DepthUnit {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => DepthUnit::Metres,
+            2 => DepthUnit::FathomsAndFeet,
+            3 => DepthUnit::Feet,
+            4 => DepthUnit::FathomsAndFractions,
+            _ => DepthUnit::Unknown,
+        }
+    }
+
+    /// Converts a depth in this unit to metres.
+    /// Fathom based values are expected in fathoms.
+    pub fn to_metres(self, value: f64) -> f64 {
+        match self {
+            DepthUnit::FathomsAndFeet | DepthUnit::FathomsAndFractions => value * 1.8288,
+            DepthUnit::Feet => value * 0.3048,
+            DepthUnit::Metres | DepthUnit::Unknown => value,
+        }
+    }
+}
+
+/// Depth range in metres
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthRange {
+    /// Shallowest depth (DRVAL1)
+    pub min: Option<f64>,
+    /// Deepest depth (DRVAL2)
+    pub max: Option<f64>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// DUNITS of the feature, metres if not given
+    pub fn depth_unit(&self) -> DepthUnit {
+        self.attribute(S57Attribute::DUNITS)
+            .and_then(|value| value.as_u32())
+            .map(DepthUnit::from_code)
+            .unwrap_or(DepthUnit::Metres)
+    }
+
+    /// DRVAL1/DRVAL2 normalized to metres
+    pub fn depth_range(&self) -> Option<DepthRange> {
+        let unit = self.depth_unit();
+        let depth = |attribute| {
+            self.attribute(attribute)
+                .and_then(|value: &AttributeValue| value.as_f64())
+                .map(|value| unit.to_metres(value))
+        };
+
+        let range = DepthRange {
+            min: depth(S57Attribute::DRVAL1),
+            max: depth(S57Attribute::DRVAL2),
+        };
+
+        if range.min.is_none() && range.max.is_none() {
+            return None;
+        }
+
+        Some(range)
+    }
+
+    /// Whether a DEPARE is deep enough for `draft_m` plus `safety_margin_m`
+    pub fn is_safe_for_draft(&self, draft_m: f64, safety_margin_m: f64) -> Option<bool> {
+        if self.s57_type() != S57Type::DEPARE {
+            return None;
+        }
+
+        let min = self.depth_range()?.min?;
+
+        Some(min >= draft_m + safety_margin_m)
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// CONVIS, 1 = visually conspicuous, 2 = not visually conspicuous
+    pub fn is_visually_conspicuous(&self) -> Option<bool> {
+        match self.attribute(S57Attribute::CONVIS)?.as_u32()? {
+            1 => Some(true),
+            2 => Some(false),
+            _ => None,
+        }
+    }
+
+    /// CONRAD, 1 = radar conspicuous, 2 = not radar conspicuous,
+    /// 3 = radar conspicuous with radar reflector
+    pub fn is_radar_conspicuous(&self) -> Option<bool> {
+        match self.attribute(S57Attribute::CONRAD)?.as_u32()? {
+            1 | 3 => Some(true),
+            2 => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum Restriction {
+    Unknown = 0,
+    AnchoringProhibited = 1,
+    AnchoringRestricted = 2,
+    FishingProhibited = 3,
+    FishingRestricted = 4,
+    TrawlingProhibited = 5,
+    TrawlingRestricted = 6,
+    EntryProhibited = 7,
+    EntryRestricted = 8,
+    DredgingProhibited = 9,
+    DredgingRestricted = 10,
+    DivingProhibited = 11,
+    DivingRestricted = 12,
+    NoWake = 13,
+    AreaToBeAvoided = 14,
+    ConstructionProhibited = 15,
+    DischargingProhibited = 16,
+    DischargingRestricted = 17,
+    ExplorationProhibited = 18,
+    ExplorationRestricted = 19,
+    DrillingProhibited = 20,
+    DrillingRestricted = 21,
+    ArtifactRemovalProhibited = 22,
+    LighteringProhibited = 23,
+    DraggingProhibited = 24,
+    StoppingProhibited = 25,
+    LandingProhibited = 26,
+    SpeedRestricted = 27,
+}
+
+#[allow(dead_code)]
+impl Restriction {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Restriction::AnchoringProhibited,
+            2 => Restriction::AnchoringRestricted,
+            3 => Restriction::FishingProhibited,
+            4 => Restriction::FishingRestricted,
+            5 => Restriction::TrawlingProhibited,
+            6 => Restriction::TrawlingRestricted,
+            7 => Restriction::EntryProhibited,
+            8 => Restriction::EntryRestricted,
+            9 => Restriction::DredgingProhibited,
+            10 => Restriction::DredgingRestricted,
+            11 => Restriction::DivingProhibited,
+            12 => Restriction::DivingRestricted,
+            13 => Restriction::NoWake,
+            14 => Restriction::AreaToBeAvoided,
+            15 => Restriction::ConstructionProhibited,
+            16 => Restriction::DischargingProhibited,
+            17 => Restriction::DischargingRestricted,
+            18 => Restriction::ExplorationProhibited,
+            19 => Restriction::ExplorationRestricted,
+            20 => Restriction::DrillingProhibited,
+            21 => Restriction::DrillingRestricted,
+            22 => Restriction::ArtifactRemovalProhibited,
+            23 => Restriction::LighteringProhibited,
+            24 => Restriction::DraggingProhibited,
+            25 => Restriction::StoppingProhibited,
+            26 => Restriction::LandingProhibited,
+            27 => Restriction::SpeedRestricted,
+            _ => Restriction::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    pub fn restrictions(&self) -> Vec<Restriction> {
+        self.attribute(S57Attribute::RESTRN)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(Restriction::from_code)
+            .collect()
+    }
+
+    /// Maintained depth of a DRGARE in metres
+    pub fn dredged_depth(&self) -> Option<f64> {
+        if self.s57_type() != S57Type::DRGARE {
+            return None;
+        }
+
+        self.depth_range()?.min
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum IceCategory {
+    Unknown = 0,
+    FastIce = 1,
+    SeaIce = 5,
+    GrowlerArea = 6,
+    PancakeIce = 7,
+    Glacier = 8,
+    IcePeak = 9,
+    PackIce = 10,
+    PolarIce = 11,
+}
+
+#[allow(dead_code)]
+impl IceCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => IceCategory::FastIce,
+            5 => IceCategory::SeaIce,
+            6 => IceCategory::GrowlerArea,
+            7 => IceCategory::PancakeIce,
+            8 => IceCategory::Glacier,
+            9 => IceCategory::IcePeak,
+            10 => IceCategory::PackIce,
+            11 => IceCategory::PolarIce,
+            _ => IceCategory::Unknown,
+        }
+    }
+}
+
+/// Recurring period from PERSTA/PEREND, as (month, day)
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeasonalPeriod {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+#[allow(dead_code)]
+impl SeasonalPeriod {
+    /// Whether the date lies in the period, periods may wrap around the new year
+    pub fn contains(&self, month: u32, day: u32) -> bool {
+        let date = (month, day);
+
+        if self.start <= self.end {
+            date >= self.start && date <= self.end
+        } else {
+            date >= self.start || date <= self.end
+        }
+    }
+}
+
+/// Month and day of an S-57 date, "CCYYMMDD" or "--MMDD"
+fn parse_month_day(date: &str) -> Option<(u32, u32)> {
+    let date = date.trim_end_matches('\0').trim();
+
+    if date.len() < 4 || !date.is_char_boundary(date.len() - 4) {
+        return None;
+    }
+
+    let month_day = &date[date.len() - 4..];
+    let month = month_day[..2].parse().ok()?;
+    let day = month_day[2..].parse().ok()?;
+
+    Some((month, day))
+}
+
+#[allow(dead_code)]
+impl S57 {
+    pub fn seasonal_period(&self) -> Option<SeasonalPeriod> {
+        let date = |attribute| {
+            self.attribute(attribute)
+                .and_then(|value: &AttributeValue| value.as_str())
+                .and_then(parse_month_day)
+        };
+
+        Some(SeasonalPeriod {
+            start: date(S57Attribute::PERSTA)?,
+            end: date(S57Attribute::PEREND)?,
+        })
+    }
+
+    pub fn ice_category(&self) -> Vec<IceCategory> {
+        if self.s57_type() != S57Type::ICEARE {
+            return Vec::new();
+        }
+
+        self.attribute(S57Attribute::CATICE)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(IceCategory::from_code)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum AirportCategory {
+    Unknown = 0,
+    MilitaryAeroplaneAirport = 1,
+    CivilAeroplaneAirport = 2,
+    MilitaryHeliport = 3,
+    CivilHeliport = 4,
+    GliderAirfield = 5,
+    SmallPlanesAirfield = 6,
+    EmergencyAirfield = 8,
+}
+
+#[allow(dead_code)]
+impl AirportCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => AirportCategory::MilitaryAeroplaneAirport,
+            2 => AirportCategory::CivilAeroplaneAirport,
+            3 => AirportCategory::MilitaryHeliport,
+            4 => AirportCategory::CivilHeliport,
+            5 => AirportCategory::GliderAirfield,
+            6 => AirportCategory::SmallPlanesAirfield,
+            8 => AirportCategory::EmergencyAirfield,
+            _ => AirportCategory::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// CATAIR of an AIRARE or SPLARE feature
+    pub fn airport_category(&self) -> Vec<AirportCategory> {
+        if self.s57_type() != S57Type::AIRARE && self.s57_type() != S57Type::SPLARE {
+            return Vec::new();
+        }
+
+        self.attribute(S57Attribute::CATAIR)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(AirportCategory::from_code)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum Jurisdiction {
+    Unknown = 0,
+    International = 1,
+    National = 2,
+    NationalSubDivision = 3,
+}
+
+#[allow(dead_code)]
+impl Jurisdiction {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Jurisdiction::International,
+            2 => Jurisdiction::National,
+            3 => Jurisdiction::NationalSubDivision,
+            _ => Jurisdiction::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// JRSDTN, e.g. of an ADMARE or maritime zone
+    pub fn jurisdiction(&self) -> Option<Jurisdiction> {
+        self.attribute(S57Attribute::JRSDTN)
+            .and_then(|value| value.as_u32())
+            .map(Jurisdiction::from_code)
+    }
+
+    /// NATION as a two letter country code
+    pub fn nation(&self) -> Option<&str> {
+        self.attribute(S57Attribute::NATION)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|nation| !nation.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum CableCategory {
+    Unknown = 0,
+    PowerLine = 1,
+    TransmissionLine = 3,
+    Telephone = 4,
+    Telegraph = 5,
+    MooringCable = 6,
+}
+
+#[allow(dead_code)]
+impl CableCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => CableCategory::PowerLine,
+            3 => CableCategory::TransmissionLine,
+            4 => CableCategory::Telephone,
+            5 => CableCategory::Telegraph,
+            6 => CableCategory::MooringCable,
+            _ => CableCategory::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum PipelineCategory {
+    Unknown = 0,
+    OutfallPipe = 2,
+    IntakePipe = 3,
+    Sewer = 4,
+    BubblerSystem = 5,
+    SupplyPipe = 6,
+}
+
+#[allow(dead_code)]
+impl PipelineCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            2 => PipelineCategory::OutfallPipe,
+            3 => PipelineCategory::IntakePipe,
+            4 => PipelineCategory::Sewer,
+            5 => PipelineCategory::BubblerSystem,
+            6 => PipelineCategory::SupplyPipe,
+            _ => PipelineCategory::Unknown,
+        }
+    }
+}
+
+/// PRODCT, what a pipeline, tank or storage area carries
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum Product {
+    Unknown = 0,
+    Oil = 1,
+    Gas = 2,
+    Water = 3,
+    Stone = 4,
+    Coal = 5,
+    Ore = 6,
+    Chemicals = 7,
+    DrinkingWater = 8,
+    Milk = 9,
+    Bauxite = 10,
+    Coke = 11,
+    IronIngots = 12,
+    Salt = 13,
+    Sand = 14,
+    Timber = 15,
+    WoodChips = 16,
+    ScrapMetal = 17,
+    LiquefiedNaturalGas = 18,
+    LiquefiedPetroleumGas = 19,
+    Wine = 20,
+    Cement = 21,
+    Grain = 22,
+}
+
+#[allow(dead_code)]
+impl Product {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Product::Oil,
+            2 => Product::Gas,
+            3 => Product::Water,
+            4 => Product::Stone,
+            5 => Product::Coal,
+            6 => Product::Ore,
+            7 => Product::Chemicals,
+            8 => Product::DrinkingWater,
+            9 => Product::Milk,
+            10 => Product::Bauxite,
+            11 => Product::Coke,
+            12 => Product::IronIngots,
+            13 => Product::Salt,
+            14 => Product::Sand,
+            15 => Product::Timber,
+            16 => Product::WoodChips,
+            17 => Product::ScrapMetal,
+            18 => Product::LiquefiedNaturalGas,
+            19 => Product::LiquefiedPetroleumGas,
+            20 => Product::Wine,
+            21 => Product::Cement,
+            22 => Product::Grain,
+            _ => Product::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// CATCBL of a CBLSUB, CBLOHD or CBLARE feature
+    pub fn cable_category(&self) -> Option<CableCategory> {
+        if !matches!(
+            self.s57_type(),
+            S57Type::CBLSUB | S57Type::CBLOHD | S57Type::CBLARE
+        ) {
+            return None;
+        }
+
+        self.attribute(S57Attribute::CATCBL)
+            .and_then(|value| value.as_u32())
+            .map(CableCategory::from_code)
+    }
+
+    /// CATPIP of a PIPSOL, PIPOHD or PIPARE feature
+    pub fn pipeline_category(&self) -> Vec<PipelineCategory> {
+        if !matches!(
+            self.s57_type(),
+            S57Type::PIPSOL | S57Type::PIPOHD | S57Type::PIPARE
+        ) {
+            return Vec::new();
+        }
+
+        self.attribute(S57Attribute::CATPIP)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(PipelineCategory::from_code)
+            .collect()
+    }
+
+    /// PRODCT, e.g. oil or gas carried by a pipeline
+    pub fn products(&self) -> Vec<Product> {
+        self.attribute(S57Attribute::PRODCT)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(Product::from_code)
+            .collect()
+    }
+}
+
+/// Calendar date of an S-57 "CCYYMMDD" attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(dead_code)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+fn parse_date(date: &str) -> Option<Date> {
+    let date = date.trim_end_matches('\0').trim();
+
+    if date.len() != 8 || !date.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let date = Date {
+        year: date[..4].parse().ok()?,
+        month: date[4..6].parse().ok()?,
+        day: date[6..].parse().ok()?,
+    };
+
+    if !(1..=12).contains(&date.month) || !(1..=31).contains(&date.day) {
+        return None;
+    }
+
+    Some(date)
+}
+
+#[allow(dead_code)]
+impl S57 {
+    fn date(&self, attribute: S57Attribute) -> Option<Date> {
+        self.attribute(attribute)
+            .and_then(|value| value.as_str())
+            .and_then(parse_date)
+    }
+
+    /// RECDAT, the date the data was recorded
+    pub fn recording_date(&self) -> Option<Date> {
+        self.date(S57Attribute::RECDAT)
+    }
+
+    /// SURSTA and SUREND, start and end of the survey
+    pub fn survey_period(&self) -> Option<(Date, Date)> {
+        Some((
+            self.date(S57Attribute::SURSTA)?,
+            self.date(S57Attribute::SUREND)?,
+        ))
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// TXTDSC, file name of the text description
+    pub fn text_description_file(&self) -> Option<&str> {
+        self.attribute(S57Attribute::TXTDSC)
+            .and_then(|value| value.as_str())
+            .map(|file| file.trim_end_matches('\0').trim())
+            .filter(|file| !file.is_empty())
+    }
+
+    /// NTXTDS, file name of the text description in the national language
+    pub fn national_text_description_file(&self) -> Option<&str> {
+        self.attribute(S57Attribute::NTXTDS)
+            .and_then(|value| value.as_str())
+            .map(|file| file.trim_end_matches('\0').trim())
+            .filter(|file| !file.is_empty())
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// POSACC, positional accuracy in metres
+    pub fn position_accuracy_m(&self) -> Option<f64> {
+        self.attribute(S57Attribute::POSACC)
+            .and_then(|value| value.as_f64())
+    }
+
+    /// Closed circle of POSACC radius around a point feature, one vertex
+    /// every `step_deg` degrees
+    pub fn uncertainty_circle(&self, step_deg: f64) -> Option<MultiGeometry> {
+        let radius = self.position_accuracy_m()?;
+
+        self.point_geometry()
+            .map(|position| position.arc(radius, 0.0, 360.0, step_deg))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum MooringCategory {
+    Unknown = 0,
+    Dolphin = 1,
+    DeviationDolphin = 2,
+    Bollard = 3,
+    TieUpWall = 4,
+    PostOrPile = 5,
+    ChainWireCable = 6,
+    MooringBuoy = 7,
+}
+
+#[allow(dead_code)]
+impl MooringCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => MooringCategory::Dolphin,
+            2 => MooringCategory::DeviationDolphin,
+            3 => MooringCategory::Bollard,
+            4 => MooringCategory::TieUpWall,
+            5 => MooringCategory::PostOrPile,
+            6 => MooringCategory::ChainWireCable,
+            7 => MooringCategory::MooringBuoy,
+            _ => MooringCategory::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// OBJNAM of a BERTHS feature, the berth number
+    pub fn berth_number(&self) -> Option<&str> {
+        if self.s57_type() != S57Type::BERTHS {
+            return None;
+        }
+
+        self.attribute(S57Attribute::OBJNAM)
+            .and_then(|value| value.as_str())
+            .map(|name| name.trim_end_matches('\0').trim())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// CATMOR of a MORFAC feature
+    pub fn mooring_category(&self) -> Vec<MooringCategory> {
+        if self.s57_type() != S57Type::MORFAC {
+            return Vec::new();
+        }
+
+        self.attribute(S57Attribute::CATMOR)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(MooringCategory::from_code)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum SurveyType {
+    Unknown = 0,
+    Reconnaissance = 1,
+    Controlled = 2,
+    Examination = 4,
+    Passage = 5,
+    RemotelySensed = 6,
+}
+
+#[allow(dead_code)]
+impl SurveyType {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => SurveyType::Reconnaissance,
+            2 => SurveyType::Controlled,
+            4 => SurveyType::Examination,
+            5 => SurveyType::Passage,
+            6 => SurveyType::RemotelySensed,
+            _ => SurveyType::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum SoundingTechnique {
+    Unknown = 0,
+    EchoSounder = 1,
+    SideScanSonar = 2,
+    MultiBeam = 3,
+    Diver = 4,
+    LeadLine = 5,
+    WireDrag = 6,
+    Laser = 7,
+    VerticalAcousticSystem = 8,
+    ElectromagneticSensor = 9,
+    Photogrammetry = 10,
+    SatelliteImagery = 11,
+    Levelling = 12,
+    SideScanSonarSwept = 13,
+    ComputerGenerated = 14,
+}
+
+#[allow(dead_code)]
+impl SoundingTechnique {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => SoundingTechnique::EchoSounder,
+            2 => SoundingTechnique::SideScanSonar,
+            3 => SoundingTechnique::MultiBeam,
+            4 => SoundingTechnique::Diver,
+            5 => SoundingTechnique::LeadLine,
+            6 => SoundingTechnique::WireDrag,
+            7 => SoundingTechnique::Laser,
+            8 => SoundingTechnique::VerticalAcousticSystem,
+            9 => SoundingTechnique::ElectromagneticSensor,
+            10 => SoundingTechnique::Photogrammetry,
+            11 => SoundingTechnique::SatelliteImagery,
+            12 => SoundingTechnique::Levelling,
+            13 => SoundingTechnique::SideScanSonarSwept,
+            14 => SoundingTechnique::ComputerGenerated,
+            _ => SoundingTechnique::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurveyReliability {
+    pub survey_types: Vec<SurveyType>,
+    pub techniques: Vec<SoundingTechnique>,
+    /// POSACC in metres
+    pub position_accuracy: Option<f64>,
+    /// SOUACC in metres
+    pub sounding_accuracy: Option<f64>,
+    pub survey_period: Option<(Date, Date)>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// SURTYP, TECSOU and accuracies of an M_SREL meta-feature
+    pub fn survey_reliability(&self) -> Option<SurveyReliability> {
+        if self.s57_type() != S57Type::M_SREL {
+            return None;
+        }
+
+        let list = |attribute| {
+            self.attribute(attribute)
+                .map(|value: &AttributeValue| value.as_list())
+                .unwrap_or_default()
+        };
+
+        Some(SurveyReliability {
+            survey_types: list(S57Attribute::SURTYP)
+                .into_iter()
+                .map(SurveyType::from_code)
+                .collect(),
+            techniques: list(S57Attribute::TECSOU)
+                .into_iter()
+                .map(SoundingTechnique::from_code)
+                .collect(),
+            position_accuracy: self.position_accuracy_m(),
+            sounding_accuracy: self
+                .attribute(S57Attribute::SOUACC)
+                .and_then(|value| value.as_f64()),
+            survey_period: self.survey_period(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum CanalCategory {
+    Unknown = 0,
+    Transportation = 1,
+    Drainage = 2,
+    Irrigation = 3,
+}
+
+#[allow(dead_code)]
+impl CanalCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => CanalCategory::Transportation,
+            2 => CanalCategory::Drainage,
+            3 => CanalCategory::Irrigation,
+            _ => CanalCategory::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum GateCategory {
+    Unknown = 0,
+    General = 1,
+    FloodBarrage = 2,
+    Caisson = 3,
+    Lock = 4,
+    Dyke = 5,
+    Sluice = 6,
+}
+
+#[allow(dead_code)]
+impl GateCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => GateCategory::General,
+            2 => GateCategory::FloodBarrage,
+            3 => GateCategory::Caisson,
+            4 => GateCategory::Lock,
+            5 => GateCategory::Dyke,
+            6 => GateCategory::Sluice,
+            _ => GateCategory::Unknown,
+        }
+    }
+}
+
+/// Dimensions in metres, each only if the feature encodes it
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Dimensions {
+    /// HORLEN
+    pub length: Option<f64>,
+    /// HORWID
+    pub width: Option<f64>,
+    /// HORCLR
+    pub horizontal_clearance: Option<f64>,
+    /// VERCLR
+    pub vertical_clearance: Option<f64>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// CATCAN of a CANALS feature
+    pub fn canal_category(&self) -> Vec<CanalCategory> {
+        if self.s57_type() != S57Type::CANALS {
+            return Vec::new();
+        }
+
+        self.attribute(S57Attribute::CATCAN)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(CanalCategory::from_code)
+            .collect()
+    }
+
+    /// CATGAT of a GATCON feature
+    pub fn gate_category(&self) -> Vec<GateCategory> {
+        if self.s57_type() != S57Type::GATCON {
+            return Vec::new();
+        }
+
+        self.attribute(S57Attribute::CATGAT)
+            .map(|value| value.as_list())
+            .unwrap_or_default()
+            .into_iter()
+            .map(GateCategory::from_code)
+            .collect()
+    }
+
+    /// Length, width and clearances, e.g. of a canal, lock basin or gate
+    pub fn dimensions(&self) -> Dimensions {
+        let metres = |attribute| {
+            self.attribute(attribute)
+                .and_then(|value: &AttributeValue| value.as_f64())
+        };
+
+        Dimensions {
+            length: metres(S57Attribute::HORLEN),
+            width: metres(S57Attribute::HORWID),
+            horizontal_clearance: metres(S57Attribute::HORCLR),
+            vertical_clearance: metres(S57Attribute::VERCLR),
+        }
+    }
+}
+
+const METRES_PER_NAUTICAL_MILE: f64 = 1852.0;
+
+#[allow(dead_code)]
+impl S57 {
+    /// VALNMR, nominal range in nautical miles
+    pub fn nominal_range_nm(&self) -> Option<f64> {
+        self.attribute(S57Attribute::VALNMR)
+            .and_then(|value| value.as_f64())
+    }
+
+    /// Arc at the nominal range around a LIGHTS feature, one vertex every
+    /// `step_deg` degrees. Limited to the sector SECTR1 to SECTR2 if given,
+    /// a full circle otherwise.
+    pub fn range_arc(&self, step_deg: f64) -> Option<MultiGeometry> {
+        if self.s57_type() != S57Type::LIGHTS {
+            return None;
+        }
+
+        let position = self.point_geometry()?;
+        let radius = self.nominal_range_nm()? * METRES_PER_NAUTICAL_MILE;
+
+        let sector = |attribute| {
+            self.attribute(attribute)
+                .and_then(|value: &AttributeValue| value.as_f64())
+        };
+
+        // sector limits are bearings from seaward towards the light
+        let (start, end) = match (sector(S57Attribute::SECTR1), sector(S57Attribute::SECTR2)) {
+            (Some(start), Some(end)) => (start + 180.0, end + 180.0),
+            _ => (0.0, 360.0),
+        };
+
+        Some(position.arc(radius, start, end, step_deg))
+    }
+
+    /// Distance in nautical miles at which the light is first seen from a
+    /// height of eye of `observer_height_m` metres: the geographic range
+    /// 2.08 * (sqrt(HEIGHT) + sqrt(height of eye)), limited by VALNMR
+    pub fn geographic_range_nm(&self, observer_height_m: f64) -> Option<f64> {
+        let height = self
+            .attribute(S57Attribute::HEIGHT)
+            .and_then(|value| value.as_f64())?;
+
+        let geographic = 2.08 * (height.max(0.0).sqrt() + observer_height_m.max(0.0).sqrt());
+
+        Some(match self.nominal_range_nm() {
+            Some(nominal) => geographic.min(nominal),
+            None => geographic,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum PilotBoardingCategory {
+    Unknown = 0,
+    CruisingVessel = 1,
+    Helicopter = 2,
+    FromShore = 3,
+}
+
+#[allow(dead_code)]
+impl PilotBoardingCategory {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => PilotBoardingCategory::CruisingVessel,
+            2 => PilotBoardingCategory::Helicopter,
+            3 => PilotBoardingCategory::FromShore,
+            _ => PilotBoardingCategory::Unknown,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PilotBoarding {
+    pub categories: Vec<PilotBoardingCategory>,
+    /// VHF channels (COMCHA), e.g. "16" or "09;12"
+    pub channels: Vec<String>,
+    /// Pilot district (PILDST)
+    pub district: Option<String>,
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// CATPIL, COMCHA and PILDST of a PILBOP feature
+    pub fn pilot_boarding(&self) -> Option<PilotBoarding> {
+        if self.s57_type() != S57Type::PILBOP {
+            return None;
+        }
+
+        let text = |attribute| {
+            self.attribute(attribute)
+                .and_then(|value: &AttributeValue| value.as_str())
+                .map(|text| text.trim_end_matches('\0').trim())
+                .filter(|text| !text.is_empty())
+        };
+
+        Some(PilotBoarding {
+            categories: self
+                .attribute(S57Attribute::CATPIL)
+                .map(|value| value.as_list())
+                .unwrap_or_default()
+                .into_iter()
+                .map(PilotBoardingCategory::from_code)
+                .collect(),
+            channels: text(S57Attribute::COMCHA)
+                .map(|channels| {
+                    channels
+                        .split([';', ','])
+                        .map(str::trim)
+                        .filter(|channel| !channel.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            district: text(S57Attribute::PILDST).map(str::to_string),
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// SCODE, symbol name chosen by the producer. Renderers honouring
+    /// pre-authored symbology draw this instead of the look-up table result.
+    pub fn symbolization_code(&self) -> Option<&str> {
+        self.attribute(S57Attribute::SCODE)
+            .and_then(|value| value.as_str())
+            .map(|code| code.trim_end_matches('\0').trim())
+            .filter(|code| !code.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{chartfile::ChartFile, test_support::ChartBuilder};
+
+    fn parse(builder: ChartBuilder) -> ChartFile {
+        ChartFile::parse_file(&mut Cursor::new(builder.build())).unwrap()
+    }
+
+    /// First feature of the chart, as a point at the cell center
+    fn point_feature(builder: ChartBuilder) -> S57 {
+        parse(builder.point(54.5, 10.5)).features()[0].clone()
+    }
+
+    #[test]
+    fn wgs84_and_mllw_datums() {
+        let chart = parse(
+            ChartBuilder::new("DATUMS")
+                .feature(S57Type::M_HDAT, 1, 3)
+                .int_attribute(S57Attribute::HORDAT as u16, 2)
+                .feature(S57Type::M_VDAT, 2, 3)
+                .int_attribute(S57Attribute::VERDAT as u16, 12),
+        );
+
+        assert_eq!(chart.horizontal_datum(), Some(HorizontalDatum::Wgs84));
+        assert_eq!(
+            chart.vertical_datum(),
+            Some(VerticalDatum::MeanLowerLowWater)
+        );
+        assert_eq!(
+            chart.features()[0].horizontal_datum(),
+            Some(HorizontalDatum::Wgs84)
+        );
+        assert_eq!(chart.features()[1].horizontal_datum(), None);
+    }
+
+    fn port_buoy(status: Option<u32>) -> S57 {
+        let mut builder = ChartBuilder::new("MARKS")
+            .feature(S57Type::BOYLAT, 1, 1)
+            .int_attribute(S57Attribute::CATLAM as u16, 1);
+        if let Some(status) = status {
+            builder = builder.int_attribute(S57Attribute::STATUS as u16, status);
+        }
+        let bytes = builder.point(54.5, 10.5).build();
+
+        let chart = ChartFile::parse_file(&mut Cursor::new(bytes)).unwrap();
+        chart.features()[0].clone()
+    }
+
+    #[test]
+    fn port_mark_colour_depends_on_region() {
+        let buoy = port_buoy(None);
+
+        assert_eq!(
+            buoy.effective_colours(BuoyageSystem::IalaA),
+            vec![S52Colour::Red]
+        );
+        assert_eq!(
+            buoy.effective_colours(BuoyageSystem::IalaB),
+            vec![S52Colour::Green]
+        );
+    }
+
+    #[test]
+    fn out_of_use_mark_has_no_colour() {
+        let buoy = port_buoy(Some(4));
+
+        assert!(buoy.effective_colours(BuoyageSystem::IalaA).is_empty());
+    }
+
+    #[test]
+    fn distance_mark_in_kilometres() {
+        let mark = point_feature(
+            ChartBuilder::new("DISMAR")
+                .feature(S57Type::DISMAR, 1, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "km 12,5")
+                .int_attribute(S57Attribute::CATDIS as u16, 3),
+        );
+
+        assert_eq!(mark.distance_mark(), Some(12500.0));
+        assert_eq!(
+            mark.distance_mark_category(),
+            Some(DistanceMarkCategory::VisibleMarkBoard)
+        );
+    }
+
+    #[test]
+    fn ais_radio_station() {
+        let station = point_feature(
+            ChartBuilder::new("RDOSTA")
+                .feature(S57Type::RDOSTA, 1, 1)
+                .string_attribute(S57Attribute::CATROS as u16, "10,16")
+                .int_attribute(S57Attribute::ESTRNG as u16, 20),
+        );
+
+        assert_eq!(
+            station.radio_station(),
+            Some(RadioStation {
+                categories: vec![
+                    RadioStationCategory::DifferentialGps,
+                    RadioStationCategory::AisBaseStation
+                ],
+                estimated_range: Some(20.0),
+                nominal_range: None,
+            })
+        );
+    }
+
+    #[test]
+    fn left_aligned_text_above_its_position() {
+        let text = point_feature(
+            ChartBuilder::new("TEXTS")
+                .feature(S57Type::TEXTS, 1, 1)
+                .string_attribute(S57Attribute::TXSTR as u16, "Fehmarnbelt")
+                .int_attribute(S57Attribute::JUSTH as u16, 3)
+                .int_attribute(S57Attribute::JUSTV as u16, 1),
+        );
+
+        assert_eq!(
+            text.text(),
+            Some(ChartText {
+                text: "Fehmarnbelt".to_string(),
+                position: Position {
+                    lat: 54.5,
+                    lon: 10.5
+                },
+                horizontal_justification: Some(HorizontalJustification::Left),
+                vertical_justification: Some(VerticalJustification::Bottom),
+            })
+        );
+    }
+
+    #[test]
+    fn preferred_channel_mark_under_both_systems() {
+        let buoy = point_feature(
+            ChartBuilder::new("MARKS")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .int_attribute(S57Attribute::CATLAM as u16, 3),
+        );
+
+        assert_eq!(
+            buoy.lateral_mark_side(BuoyageSystem::IalaA),
+            Some(LateralSide {
+                side: Side::Port,
+                colour: S52Colour::Red,
+                preferred_channel: true,
+            })
+        );
+        assert_eq!(
+            buoy.lateral_mark_side(BuoyageSystem::IalaB),
+            Some(LateralSide {
+                side: Side::Port,
+                colour: S52Colour::Green,
+                preferred_channel: true,
+            })
+        );
+        assert_eq!(buoy.lateral_mark_side(BuoyageSystem::NoSystem), None);
+    }
+
+    #[test]
+    fn one_way_fairway() {
+        let fairway = point_feature(
+            ChartBuilder::new("FAIRWY")
+                .feature(S57Type::FAIRWY, 1, 3)
+                .int_attribute(S57Attribute::ORIENT as u16, 45)
+                .int_attribute(S57Attribute::TRAFIC as u16, 3),
+        );
+
+        assert_eq!(
+            fairway.fairway(),
+            Some(FairwayInfo {
+                orientation: Some(45.0),
+                traffic_flow: Some(TrafficFlow::OneWay),
+            })
+        );
+        assert_eq!(port_buoy(None).fairway(), None);
+    }
+
+    #[test]
+    fn depth_area_against_draft() {
+        let depth_area = |unit: u32| {
+            point_feature(
+                ChartBuilder::new("DEPARE")
+                    .feature(S57Type::DEPARE, 1, 3)
+                    .int_attribute(S57Attribute::DRVAL1 as u16, 10)
+                    .int_attribute(S57Attribute::DRVAL2 as u16, 20)
+                    .int_attribute(S57Attribute::DUNITS as u16, unit),
+            )
+        };
+
+        let metres = depth_area(1);
+        assert_eq!(metres.is_safe_for_draft(8.0, 2.0), Some(true));
+        assert_eq!(metres.is_safe_for_draft(8.0, 2.5), Some(false));
+
+        // 10 ft is about 3 m
+        let feet = depth_area(3);
+        assert_eq!(feet.is_safe_for_draft(2.5, 0.5), Some(true));
+        assert_eq!(feet.is_safe_for_draft(3.0, 0.5), Some(false));
+
+        assert_eq!(port_buoy(None).is_safe_for_draft(2.0, 0.0), None);
+    }
+
+    #[test]
+    fn conspicuous_landmark_with_radar_reflector() {
+        let landmark = point_feature(
+            ChartBuilder::new("LNDMRK")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .int_attribute(S57Attribute::CONVIS as u16, 1)
+                .int_attribute(S57Attribute::CONRAD as u16, 3),
+        );
+        assert_eq!(landmark.is_visually_conspicuous(), Some(true));
+        assert_eq!(landmark.is_radar_conspicuous(), Some(true));
+
+        let tower = point_feature(
+            ChartBuilder::new("LNDMRK")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .int_attribute(S57Attribute::CONVIS as u16, 2),
+        );
+        assert_eq!(tower.is_visually_conspicuous(), Some(false));
+        assert_eq!(tower.is_radar_conspicuous(), None);
+    }
+
+    #[test]
+    fn dredged_area_with_restrictions() {
+        let area = point_feature(
+            ChartBuilder::new("DRGARE")
+                .feature(S57Type::DRGARE, 1, 3)
+                .int_attribute(S57Attribute::DRVAL1 as u16, 12)
+                .string_attribute(S57Attribute::RESTRN as u16, "1,13"),
+        );
+
+        assert_eq!(area.dredged_depth(), Some(12.0));
+        assert_eq!(
+            area.restrictions(),
+            vec![Restriction::AnchoringProhibited, Restriction::NoWake]
+        );
+        assert_eq!(port_buoy(None).dredged_depth(), None);
+    }
+
+    #[test]
+    fn winter_pack_ice_area() {
+        let ice = point_feature(
+            ChartBuilder::new("ICEARE")
+                .feature(S57Type::ICEARE, 1, 3)
+                .string_attribute(S57Attribute::CATICE as u16, "10")
+                .string_attribute(S57Attribute::PERSTA as u16, "--1201")
+                .string_attribute(S57Attribute::PEREND as u16, "20240315"),
+        );
+
+        assert_eq!(ice.ice_category(), vec![IceCategory::PackIce]);
+
+        let period = ice.seasonal_period().unwrap();
+        assert_eq!(
+            period,
+            SeasonalPeriod {
+                start: (12, 1),
+                end: (3, 15),
+            }
+        );
+        assert!(period.contains(1, 20));
+        assert!(period.contains(12, 1));
+        assert!(!period.contains(7, 1));
+    }
+
+    #[test]
+    fn seaplane_landing_area_category() {
+        let area = point_feature(
+            ChartBuilder::new("SPLARE")
+                .feature(S57Type::SPLARE, 1, 3)
+                .string_attribute(S57Attribute::CATAIR as u16, "2,4"),
+        );
+
+        assert_eq!(
+            area.airport_category(),
+            vec![
+                AirportCategory::CivilAeroplaneAirport,
+                AirportCategory::CivilHeliport
+            ]
+        );
+
+        let landmark = point_feature(
+            ChartBuilder::new("LNDMRK")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .string_attribute(S57Attribute::CATAIR as u16, "2"),
+        );
+        assert!(landmark.airport_category().is_empty());
+    }
+
+    #[test]
+    fn national_boundary_jurisdiction() {
+        let zone = point_feature(
+            ChartBuilder::new("ADMARE")
+                .feature(S57Type::ADMARE, 1, 3)
+                .string_attribute(S57Attribute::NATION as u16, " DE ")
+                .int_attribute(S57Attribute::JRSDTN as u16, 2),
+        );
+        assert_eq!(zone.nation(), Some("DE"));
+        assert_eq!(zone.jurisdiction(), Some(Jurisdiction::National));
+
+        let unnamed = point_feature(
+            ChartBuilder::new("ADMARE")
+                .feature(S57Type::ADMARE, 1, 3)
+                .string_attribute(S57Attribute::NATION as u16, "  "),
+        );
+        assert_eq!(unnamed.nation(), None);
+        assert_eq!(unnamed.jurisdiction(), None);
+    }
+
+    #[test]
+    fn submarine_cable_and_gas_pipeline() {
+        let cable = point_feature(
+            ChartBuilder::new("CBLSUB")
+                .feature(S57Type::CBLSUB, 1, 2)
+                .int_attribute(S57Attribute::CATCBL as u16, 1),
+        );
+        assert_eq!(cable.cable_category(), Some(CableCategory::PowerLine));
+        assert!(cable.pipeline_category().is_empty());
+
+        let pipeline = point_feature(
+            ChartBuilder::new("PIPSOL")
+                .feature(S57Type::PIPSOL, 1, 2)
+                .string_attribute(S57Attribute::CATPIP as u16, "6")
+                .string_attribute(S57Attribute::PRODCT as u16, "2"),
+        );
+        assert_eq!(
+            pipeline.pipeline_category(),
+            vec![PipelineCategory::SupplyPipe]
+        );
+        assert_eq!(pipeline.products(), vec![Product::Gas]);
+        assert_eq!(pipeline.cable_category(), None);
+    }
+
+    #[test]
+    fn sounding_recording_date_and_survey_period() {
+        let soundings = point_feature(
+            ChartBuilder::new("SOUNDG")
+                .feature(S57Type::SOUNDG, 1, 1)
+                .string_attribute(S57Attribute::RECDAT as u16, "20190614")
+                .string_attribute(S57Attribute::SURSTA as u16, "20190601")
+                .string_attribute(S57Attribute::SUREND as u16, "20191331"),
+        );
+
+        assert_eq!(
+            soundings.recording_date(),
+            Some(Date {
+                year: 2019,
+                month: 6,
+                day: 14
+            })
+        );
+        // month 13 of the survey end is invalid
+        assert_eq!(soundings.survey_period(), None);
+    }
+
+    #[test]
+    fn uncertainty_circle_of_a_wreck() {
+        let wreck = point_feature(
+            ChartBuilder::new("WRECKS")
+                .feature(S57Type::WRECKS, 1, 1)
+                .int_attribute(S57Attribute::POSACC as u16, 50),
+        );
+        let center = Position {
+            lat: 54.5,
+            lon: 10.5,
+        };
+
+        assert_eq!(wreck.position_accuracy_m(), Some(50.0));
+
+        let circle = wreck.uncertainty_circle(10.0).unwrap();
+        assert_eq!(circle.len(), 37);
+        assert!(circle
+            .iter()
+            .all(|p| (center.distance_to(p) - 50.0).abs() < 1e-6));
+
+        assert_eq!(port_buoy(None).uncertainty_circle(10.0), None);
+    }
+
+    #[test]
+    fn berth_number_and_mooring_dolphins() {
+        let berth = point_feature(
+            ChartBuilder::new("BERTHS")
+                .feature(S57Type::BERTHS, 1, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, " 12 "),
+        );
+        assert_eq!(berth.berth_number(), Some("12"));
+        assert!(berth.mooring_category().is_empty());
+
+        let dolphins = point_feature(
+            ChartBuilder::new("MORFAC")
+                .feature(S57Type::MORFAC, 1, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "D1")
+                .string_attribute(S57Attribute::CATMOR as u16, "1,3"),
+        );
+        assert_eq!(
+            dolphins.mooring_category(),
+            vec![MooringCategory::Dolphin, MooringCategory::Bollard]
+        );
+        assert_eq!(dolphins.berth_number(), None);
+    }
+
+    #[test]
+    fn two_way_recommended_tracks() {
+        let track = |orient: &str| {
+            point_feature(
+                ChartBuilder::new("RECTRC")
+                    .feature(S57Type::RECTRC, 1, 2)
+                    .string_attribute(S57Attribute::ORIENT as u16, orient)
+                    .int_attribute(S57Attribute::TRAFIC as u16, 4),
+            )
+            .recommended_track()
+        };
+
+        assert_eq!(
+            track("270"),
+            Some(RecommendedTrack {
+                bearing: 270.0,
+                reciprocal: Some(90.0),
+                traffic_flow: Some(TrafficFlow::TwoWay),
+            })
+        );
+        assert_eq!(
+            track("045,226").and_then(|track| track.reciprocal),
+            Some(226.0)
+        );
+        assert_eq!(port_buoy(None).recommended_track(), None);
+    }
+
+    #[test]
+    fn multibeam_survey_reliability() {
+        let zone = point_feature(
+            ChartBuilder::new("M_SREL")
+                .feature(S57Type::M_SREL, 1, 3)
+                .string_attribute(S57Attribute::SURTYP as u16, "2")
+                .string_attribute(S57Attribute::TECSOU as u16, "3,2")
+                .int_attribute(S57Attribute::POSACC as u16, 5)
+                .string_attribute(S57Attribute::SOUACC as u16, "0.5")
+                .string_attribute(S57Attribute::SURSTA as u16, "20210401")
+                .string_attribute(S57Attribute::SUREND as u16, "20210930"),
+        );
+
+        assert_eq!(
+            zone.survey_reliability(),
+            Some(SurveyReliability {
+                survey_types: vec![SurveyType::Controlled],
+                techniques: vec![
+                    SoundingTechnique::MultiBeam,
+                    SoundingTechnique::SideScanSonar
+                ],
+                position_accuracy: Some(5.0),
+                sounding_accuracy: Some(0.5),
+                survey_period: Some((
+                    Date {
+                        year: 2021,
+                        month: 4,
+                        day: 1
+                    },
+                    Date {
+                        year: 2021,
+                        month: 9,
+                        day: 30
+                    }
+                )),
+            })
+        );
+        assert_eq!(port_buoy(None).survey_reliability(), None);
+    }
+
+    #[test]
+    fn lock_gate_with_clearances() {
+        let gate = point_feature(
+            ChartBuilder::new("GATCON")
+                .feature(S57Type::GATCON, 1, 2)
+                .string_attribute(S57Attribute::CATGAT as u16, "4")
+                .int_attribute(S57Attribute::HORCLR as u16, 45)
+                .string_attribute(S57Attribute::VERCLR as u16, "12.5"),
+        );
+        assert_eq!(gate.gate_category(), vec![GateCategory::Lock]);
+        assert!(gate.canal_category().is_empty());
+        assert_eq!(
+            gate.dimensions(),
+            Dimensions {
+                horizontal_clearance: Some(45.0),
+                vertical_clearance: Some(12.5),
+                ..Default::default()
+            }
+        );
+
+        let canal = point_feature(
+            ChartBuilder::new("CANALS")
+                .feature(S57Type::CANALS, 1, 3)
+                .string_attribute(S57Attribute::CATCAN as u16, "1"),
+        );
+        assert_eq!(canal.canal_category(), vec![CanalCategory::Transportation]);
+        assert_eq!(canal.dimensions(), Dimensions::default());
+    }
+
+    #[test]
+    fn sector_light_range_arc() {
+        let center = Position {
+            lat: 54.5,
+            lon: 10.5,
+        };
+        let light = point_feature(
+            ChartBuilder::new("LIGHTS")
+                .feature(S57Type::LIGHTS, 1, 1)
+                .int_attribute(S57Attribute::VALNMR as u16, 10)
+                .int_attribute(S57Attribute::SECTR1 as u16, 90)
+                .int_attribute(S57Attribute::SECTR2 as u16, 180),
+        );
+
+        assert_eq!(light.nominal_range_nm(), Some(10.0));
+
+        // seen from the west to the north of the light
+        let arc = light.range_arc(45.0).unwrap();
+        assert_eq!(arc.len(), 3);
+        assert_eq!(arc[0], center.destination(270.0, 18520.0));
+        assert_eq!(arc[2], center.destination(360.0, 18520.0));
+
+        let all_round = point_feature(
+            ChartBuilder::new("LIGHTS")
+                .feature(S57Type::LIGHTS, 1, 1)
+                .int_attribute(S57Attribute::VALNMR as u16, 10),
+        );
+        assert_eq!(all_round.range_arc(90.0).unwrap().len(), 5);
+        assert_eq!(port_buoy(None).range_arc(90.0), None);
+    }
+
+    #[test]
+    fn light_range_from_height_of_eye() {
+        let light = |nominal: Option<u32>| {
+            let mut builder = ChartBuilder::new("LIGHTS")
+                .feature(S57Type::LIGHTS, 1, 1)
+                .int_attribute(S57Attribute::HEIGHT as u16, 36);
+            if let Some(nominal) = nominal {
+                builder = builder.int_attribute(S57Attribute::VALNMR as u16, nominal);
+            }
+            point_feature(builder)
+        };
+
+        // 2.08 * (6 + 2)
+        let range = light(None).geographic_range_nm(4.0).unwrap();
+        assert!((range - 16.64).abs() < 1e-9);
+        assert_eq!(light(Some(10)).geographic_range_nm(4.0), Some(10.0));
+        assert_eq!(port_buoy(None).geographic_range_nm(4.0), None);
+    }
+
+    #[test]
+    fn pilot_boarding_by_vessel_and_helicopter() {
+        let boarding = point_feature(
+            ChartBuilder::new("PILBOP")
+                .feature(S57Type::PILBOP, 1, 1)
+                .string_attribute(S57Attribute::CATPIL as u16, "1,2")
+                .string_attribute(S57Attribute::COMCHA as u16, "09; 12;")
+                .string_attribute(S57Attribute::PILDST as u16, "Kiel"),
+        );
+
+        assert_eq!(
+            boarding.pilot_boarding(),
+            Some(PilotBoarding {
+                categories: vec![
+                    PilotBoardingCategory::CruisingVessel,
+                    PilotBoardingCategory::Helicopter
+                ],
+                channels: vec!["09".to_string(), "12".to_string()],
+                district: Some("Kiel".to_string()),
+            })
+        );
+        assert_eq!(port_buoy(None).pilot_boarding(), None);
+    }
+
+    #[test]
+    fn producer_symbol_code() {
+        let mark = point_feature(
+            ChartBuilder::new("SCODE")
+                .feature(S57Type::BCNSPP, 1, 1)
+                .string_attribute(S57Attribute::SCODE as u16, " BCNSPP21 "),
+        );
+        assert_eq!(mark.symbolization_code(), Some("BCNSPP21"));
+
+        let blank = point_feature(
+            ChartBuilder::new("SCODE")
+                .feature(S57Type::BCNSPP, 1, 1)
+                .string_attribute(S57Attribute::SCODE as u16, ""),
+        );
+        assert_eq!(blank.symbolization_code(), None);
+        assert_eq!(port_buoy(None).symbolization_code(), None);
+    }
+}
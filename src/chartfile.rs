@@ -17,31 +17,234 @@
  */
 
 use std::{
-    ffi::{c_char, CStr},
-    io::{ErrorKind, Read, Seek, SeekFrom},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+    io::{Read, Seek, SeekFrom},
+    ops::ControlFlow,
+    time::{Duration, Instant, SystemTime},
 };
 
 use crate::{
-    s57::{self, LineElement, PointGeometry, Position, Rect, S57Attribute, S57},
+    attributes::{HorizontalDatum, S52Colour, SoundingDatum, VerticalDatum},
+    error::ChartError,
+    geometry,
+    s57::{
+        self, AttributeKind, AttributeValue, ConnectedNode, GeometryError, GeometryPrimitive,
+        LineElement, MultiGeometry, PointGeometry, Position, Rect, S57Attribute, S57Type,
+        VectorEdge, S57,
+    },
+    soundings::SoundingIndex,
     types::{
-        OsencAreaGeometryRecordPayload, OsencAttributeRecordPayload, OsencExtentRecordPayload,
-        OsencFeatureIdentificationRecordPayload, OsencLineGeometryRecordPayload,
-        OsencMultipointGeometryRecordPayload, OsencPointGeometryRecordPayload, OsencRecordBase,
-        OsencServerstatRecordPayload,
+        FromLeBytes, OsencAreaGeometryRecordPayload, OsencAttributeRecordPayload,
+        OsencExtentRecordPayload, OsencFeatureIdentificationRecordPayload,
+        OsencLineGeometryRecordPayload, OsencMultipointGeometryRecordPayload,
+        OsencPointGeometryRecordPayload, OsencRecordBase, OsencServerstatRecordPayload,
     },
 };
 
 #[allow(dead_code)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChartFile {
     extent: Rect,
+    /// SW, NW, NE and SE corners as stored in the extent record
+    extent_corners: [Position; 4],
     s57: Vec<S57>,
+    vector_edges: HashMap<u32, VectorEdge>,
+    connected_nodes: HashMap<u32, ConnectedNode>,
     name: String,
     publishdate: String,
     edition: u16,
     updatedate: String,
     update: u16,
+    applied_updates: Vec<u16>,
     nativescale: u32,
     soundingdatum: String,
+    warnings: Vec<ParseWarning>,
+    subscription_status: Option<SubscriptionStatus>,
+    status: ChartStatus,
+    parsed_at: Option<SystemTime>,
+    parse_duration: Option<Duration>,
+    report: ParseReport,
+}
+
+/// Summary of everything the parser skipped or could not attach
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseReport {
+    /// Number of records skipped without decoding, by record type
+    pub skipped_records: BTreeMap<u16, usize>,
+    /// Attribute and geometry records before any feature id record
+    pub orphaned_records: usize,
+    /// Attributes with a type code missing from the S-57 catalogue
+    pub unknown_attributes: usize,
+    /// Number of [`ParseWarning`]s collected
+    pub warnings: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubscriptionStatus {
+    Valid,
+    InGrace { days_remaining: u16 },
+    Expired,
+}
+
+impl SubscriptionStatus {
+    fn from_server_status(record: &OsencServerstatRecordPayload) -> Self {
+        if record.get_expire_status() != 0 {
+            SubscriptionStatus::Valid
+        } else if record.get_grace_days_allowed() > 0 && record.get_grace_days_remaining() > 0 {
+            SubscriptionStatus::InGrace {
+                days_remaining: record.get_grace_days_remaining(),
+            }
+        } else {
+            SubscriptionStatus::Expired
+        }
+    }
+}
+
+/// Expiry and grace period as reported by the server status record.
+/// All zero if the file has no such record.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChartStatus {
+    /// Subscription expired and no grace days left
+    pub expired: bool,
+    pub expire_days_remaining: u16,
+    pub grace_days_allowed: u16,
+    pub grace_days_remaining: u16,
+}
+
+impl ChartStatus {
+    fn from_server_status(record: &OsencServerstatRecordPayload) -> Self {
+        ChartStatus {
+            expired: SubscriptionStatus::from_server_status(record) == SubscriptionStatus::Expired,
+            expire_days_remaining: record.get_expire_days_remaining(),
+            grace_days_allowed: record.get_grace_days_allowed(),
+            grace_days_remaining: record.get_grace_days_remaining(),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartVersion {
+    pub edition: u16,
+    pub update: u16,
+    pub updatedate: String,
+    pub publishdate: String,
+}
+
+/// Snapshot of the chart metadata for info pages and templates
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChartSummary {
+    pub name: String,
+    pub scale: u32,
+    pub edition: u16,
+    pub update: u16,
+    /// `[lon, lat]` of the NW, NE, SE and SW corners of the extent
+    pub extent_corners: [[f64; 2]; 4],
+    /// Feature count per object class acronym
+    pub feature_counts: BTreeMap<String, usize>,
+    pub subscription_status: Option<SubscriptionStatus>,
+}
+
+/// Formats an OSENC date ("20240301") as "2024-03-01"
+fn format_date(date: &str) -> String {
+    let date = date.trim_end_matches('\0').trim();
+
+    if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) {
+        format!("{}-{}-{}", &date[0..4], &date[4..6], &date[6..8])
+    } else {
+        date.to_string()
+    }
+}
+
+/// Lowercases, folds common Latin diacritics to ASCII and collapses whitespace
+fn normalize_name(name: &str) -> String {
+    let folded: String = name
+        .trim_end_matches('\0')
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            _ => c,
+        })
+        .collect();
+
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl fmt::Display for ChartVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ed. {}, Upd. {}", self.edition, self.update)?;
+
+        let date = if self.updatedate.trim_end_matches('\0').is_empty() {
+            format_date(&self.publishdate)
+        } else {
+            format_date(&self.updatedate)
+        };
+
+        if !date.is_empty() {
+            write!(f, " ({})", date)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Attribute whose stored value doesn't match its catalogue type
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeValidationError {
+    /// Index into `features()`
+    pub feature_index: usize,
+    pub attribute: S57Attribute,
+    pub expected: AttributeKind,
+    pub value: AttributeValue,
+}
+
+/// Geometry of a feature that doesn't fit its declared primitive
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrimitiveMismatch {
+    /// No geometry for the declared primitive
+    Missing(GeometryPrimitive),
+    /// Geometry of another primitive is populated as well
+    Unexpected {
+        declared: GeometryPrimitive,
+        found: GeometryPrimitive,
+    },
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseWarning {
+    /// Attribute value encoding the parser does not decode
+    UnhandledValueType { attribute_code: u16, value_type: u8 },
+    /// Float attribute encoded with an int value type, read as double
+    ValueTypeMismatch { attribute_code: u16, value_type: u8 },
+    /// Header or server status record after the first feature record
+    RecordOutOfSection { record_type: u16 },
+    /// Line element referencing an edge or node missing from the vector tables
+    UnresolvedGeometry {
+        feature_id: u16,
+        error: GeometryError,
+    },
 }
 
 const HEADER_SENC_VERSION: u16 = 1;
@@ -76,33 +279,165 @@ const CELL_TXTDSC_INFO_FILE_RECORD: u16 = 101;
 
 const SERVER_STATUS_RECORD: u16 = 200;
 
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Feature record types to decode, everything else is skipped.
+    /// Header, cell and feature id records are always decoded.
+    pub record_filter: Option<HashSet<u16>>,
+    /// Collect [`ParseWarning`]s for data the parser skips
+    pub diagnostics: bool,
+    /// Fail on header records appearing after the first feature record
+    /// instead of only recording a warning
+    pub strict: bool,
+    /// Store the byte offset of each feature's FEATURE_ID record
+    pub record_offsets: bool,
+    /// Record when the chart was parsed and how long it took
+    pub measure_timing: bool,
+    /// Reference point for unprojecting sounding, edge and node coordinates,
+    /// the cell's extent center if `None`. The encoder projects about the
+    /// cell center, so any other reference shifts the decoded positions.
+    /// The shift grows with the distance between the two references; use a
+    /// shared reference only for cells small and close enough that this is
+    /// below display precision.
+    pub projection_reference: Option<Position>,
+    /// Keep parsing charts whose subscription has expired, including those
+    /// still in their grace period, instead of failing with
+    /// [`ChartError::Expired`], see `ChartFile::status`
+    pub allow_expired: bool,
+}
+
+impl ParseOptions {
+    fn decodes(&self, record_type: u16) -> bool {
+        match &self.record_filter {
+            Some(filter) => {
+                !(FEATURE_ATTRIBUTE_RECORD..CELL_COVR_RECORD).contains(&record_type)
+                    || filter.contains(&record_type)
+            }
+            None => true,
+        }
+    }
+}
+
 impl ChartFile {
-    pub fn parse_file<R: Read + Seek>(reader: &mut R) -> std::io::Result<ChartFile> {
+    pub fn parse_file<R: Read + Seek>(reader: &mut R) -> Result<ChartFile, ChartError> {
+        Self::parse_file_with_options(reader, &ParseOptions::default())
+    }
+
+    pub fn parse_file_with_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<ChartFile, ChartError> {
+        let mut s57_vector: Vec<S57> = Vec::new();
+
+        let mut chart = Self::parse_records(reader, options, |s57| {
+            s57_vector.push(s57);
+            ControlFlow::Continue(())
+        })?;
+        chart.s57 = s57_vector;
+
+        // the vector tables follow the feature records, so geometry can
+        // only be built once everything is read
+        let unresolved = chart.build_geometries();
+        if options.diagnostics {
+            chart
+                .warnings
+                .extend(unresolved.into_iter().map(|(index, error)| {
+                    ParseWarning::UnresolvedGeometry {
+                        feature_id: chart.s57[index].feature_id(),
+                        error,
+                    }
+                }));
+            chart.report.warnings = chart.warnings.len();
+        }
+
+        Ok(chart)
+    }
+
+    /// Reads all records, handing every feature to `on_feature` once the
+    /// next feature starts. The returned chart has no features.
+    fn parse_records<R: Read + Seek, F: FnMut(S57) -> ControlFlow<()>>(
+        reader: &mut R,
+        options: &ParseOptions,
+        mut on_feature: F,
+    ) -> Result<ChartFile, ChartError> {
+        let parse_start = Instant::now();
+
         let mut extent: Rect = Rect {
             top_left: Position { lat: 0.0, lon: 0.0 },
             bottom_right: Position { lat: 0.0, lon: 0.0 },
         };
+        let mut extent_corners = [Position { lat: 0.0, lon: 0.0 }; 4];
         let mut name = String::new();
         let mut publishdate = String::new();
-        let mut s57_vector: Vec<S57> = Vec::new();
         let mut edition = 0u16;
         let mut updatedate = String::new();
         let mut update = 0u16;
+        let mut applied_updates: Vec<u16> = Vec::new();
         let mut nativescale = 0u32;
         let mut soundingdatum = String::new();
+        let mut warnings: Vec<ParseWarning> = Vec::new();
+        let mut subscription_status: Option<SubscriptionStatus> = None;
+        let mut status = ChartStatus::default();
+        let mut skipped_records: BTreeMap<u16, usize> = BTreeMap::new();
+        let mut orphaned_records = 0usize;
+        let mut unknown_attributes = 0usize;
 
-        // let vector_edges: HashMap<u16, VectorEdge> = HashMap::new();
-        // let connected_nodes: HashMap<u16, ConnectedNode> = HashMap::new();
+        let mut vector_edges: HashMap<u32, VectorEdge> = HashMap::new();
+        let mut connected_nodes: HashMap<u32, ConnectedNode> = HashMap::new();
 
-        let mut current_s57: Option<&mut S57> = None;
+        let mut current_s57: Option<S57> = None;
+
+        let mut first_record = true;
+        let mut stopped = false;
 
         loop {
-            let mut buf = [0u8; std::mem::size_of::<OsencRecordBase>()];
-            if reader.read_exact(&mut buf).is_err() {
-                break;
+            let record_offset = if options.record_offsets {
+                Some(reader.stream_position()?)
+            } else {
+                None
+            };
+
+            let record_base: OsencRecordBase = match read_payload(reader) {
+                Ok(record_base) => record_base,
+                Err(_) => break,
+            };
+
+            let record_type = record_base.get_record_type();
+
+            // a decrypted SENC always starts with the version or server status record
+            if first_record
+                && record_type != HEADER_SENC_VERSION
+                && record_type != SERVER_STATUS_RECORD
+            {
+                return Err(ChartError::Encrypted);
+            }
+            first_record = false;
+
+            let header_record = (HEADER_SENC_VERSION..=HEADER_CELL_SOUNDINGDATUM)
+                .contains(&record_type)
+                || record_type == SERVER_STATUS_RECORD;
+
+            if header_record && current_s57.is_some() {
+                if options.strict {
+                    return Err(ChartError::UnexpectedRecord(record_type));
+                }
+
+                warnings.push(ParseWarning::RecordOutOfSection { record_type });
             }
 
-            let record_base: OsencRecordBase = unsafe { std::mem::transmute(buf) };
+            if (FEATURE_ATTRIBUTE_RECORD..=FEATURE_GEOMETRY_RECORD_AREA_EXT).contains(&record_type)
+                && current_s57.is_none()
+            {
+                orphaned_records += 1;
+            }
+
+            if !options.decodes(record_base.get_record_type()) {
+                let buf_size = payload_size(&record_base)?;
+
+                reader.seek(SeekFrom::Current(buf_size as i64))?;
+                *skipped_records.entry(record_type).or_default() += 1;
+                continue;
+            }
 
             match record_base.get_record_type() {
                 0 => {
@@ -111,59 +446,45 @@ impl ChartFile {
                 }
                 SERVER_STATUS_RECORD => {
                     if record_base.get_record_len() >= 20 {
-                        return Err(std::io::Error::new(
-                            ErrorKind::Other,
-                            "Failed to parse header",
-                        ));
+                        return Err(ChartError::MalformedHeader);
                     }
 
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
-                    assert_eq!(
-                        buf_size,
-                        std::mem::size_of::<OsencServerstatRecordPayload>()
-                    );
-                    let mut buf = [0u8; std::mem::size_of::<OsencServerstatRecordPayload>()];
+                    expect_payload_size(buf_size, OsencServerstatRecordPayload::SIZE)?;
 
-                    reader.read_exact(&mut buf)?;
+                    let serverstat_record: OsencServerstatRecordPayload = read_payload(reader)?;
 
-                    let serverstat_record: OsencServerstatRecordPayload =
-                        unsafe { std::mem::transmute(buf) };
+                    status = ChartStatus::from_server_status(&serverstat_record);
+                    subscription_status =
+                        Some(SubscriptionStatus::from_server_status(&serverstat_record));
 
-                    if serverstat_record.get_expire_status() == 0 {
-                        return Err(std::io::Error::new(ErrorKind::Other, "Chart expired"));
+                    // charts in their grace period have expired too
+                    if serverstat_record.get_expire_status() == 0 && !options.allow_expired {
+                        return Err(ChartError::Expired);
                     }
 
                     if serverstat_record.get_decrypt_status() == 0 {
-                        return Err(std::io::Error::new(ErrorKind::Other, "Signature failure"));
+                        return Err(ChartError::Encrypted);
                     }
                 }
                 HEADER_SENC_VERSION => {
                     if record_base.get_record_len() < 6 || record_base.get_record_len() >= 16 {
-                        return Err(std::io::Error::new(
-                            ErrorKind::Other,
-                            "Failed to parse header",
-                        ));
+                        return Err(ChartError::MalformedHeader);
                     }
 
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
-
-                    assert_eq!(buf_size, std::mem::size_of::<u16>());
-                    let mut buf = [0u8; std::mem::size_of::<u16>()];
+                    let buf_size = payload_size(&record_base)?;
 
-                    reader.read_exact(&mut buf)?;
+                    expect_payload_size(buf_size, std::mem::size_of::<u16>())?;
 
-                    let version: u16 = unsafe { std::mem::transmute(buf) };
+                    let version = read_u16(reader)?;
 
                     if version < 201 {
-                        return Err(std::io::Error::new(ErrorKind::Other, "Unsupported Version"));
+                        return Err(ChartError::UnsupportedVersion(version));
                     }
                 }
                 HEADER_CELL_NAME => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     let mut buf = vec![0u8; buf_size];
 
@@ -175,8 +496,7 @@ impl ChartFile {
                 }
 
                 HEADER_CELL_PUBLISHDATE => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     let mut buf = vec![0u8; buf_size];
 
@@ -187,22 +507,14 @@ impl ChartFile {
                     }
                 }
                 HEADER_CELL_EDITION => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
-
-                    assert_eq!(buf_size, std::mem::size_of::<u16>());
+                    let buf_size = payload_size(&record_base)?;
 
-                    let mut buf = [0u8; std::mem::size_of::<u16>()];
+                    expect_payload_size(buf_size, std::mem::size_of::<u16>())?;
 
-                    reader.read_exact(&mut buf)?;
-
-                    let cell_edition: u16 = unsafe { std::mem::transmute(buf) };
-
-                    edition = cell_edition;
+                    edition = read_u16(reader)?;
                 }
                 HEADER_CELL_UPDATEDATE => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     let mut buf = vec![0u8; buf_size];
 
@@ -213,37 +525,25 @@ impl ChartFile {
                     }
                 }
                 HEADER_CELL_UPDATE => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
-
-                    assert_eq!(buf_size, std::mem::size_of::<u16>());
+                    let buf_size = payload_size(&record_base)?;
 
-                    let mut buf = [0u8; std::mem::size_of::<u16>()];
+                    expect_payload_size(buf_size, std::mem::size_of::<u16>())?;
 
-                    reader.read_exact(&mut buf)?;
-
-                    let cell_update: u16 = unsafe { std::mem::transmute(buf) };
-
-                    update = cell_update;
+                    update = read_u16(reader)?;
+                    if update > 0 {
+                        applied_updates.push(update);
+                    }
                 }
                 HEADER_CELL_NATIVESCALE => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
-
-                    assert_eq!(buf_size, std::mem::size_of::<u32>());
-
-                    let mut buf = [0u8; std::mem::size_of::<u32>()];
-
-                    reader.read_exact(&mut buf)?;
+                    let buf_size = payload_size(&record_base)?;
 
-                    let cell_nativescale: u32 = unsafe { std::mem::transmute(buf) };
+                    expect_payload_size(buf_size, std::mem::size_of::<u32>())?;
 
-                    nativescale = cell_nativescale;
+                    nativescale = read_u32(reader)?;
                 }
 
                 HEADER_CELL_SOUNDINGDATUM => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     let mut buf = vec![0u8; buf_size];
 
@@ -255,24 +555,18 @@ impl ChartFile {
                 }
 
                 HEADER_CELL_SENCCREATEDATE => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
 
                 CELL_EXTENT_RECORD => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
-                    assert_eq!(buf_size, std::mem::size_of::<OsencExtentRecordPayload>());
+                    expect_payload_size(buf_size, OsencExtentRecordPayload::SIZE)?;
 
-                    let mut buf = [0u8; std::mem::size_of::<OsencExtentRecordPayload>()];
-
-                    reader.read_exact(&mut buf)?;
-
-                    let cell_extent_record: OsencExtentRecordPayload =
-                        unsafe { std::mem::transmute(buf) };
+                    let cell_extent_record: OsencExtentRecordPayload = read_payload(reader)?;
 
                     extent.top_left = Position {
                         lat: cell_extent_record.extent_nw_lat,
@@ -283,75 +577,101 @@ impl ChartFile {
                         lat: cell_extent_record.extent_se_lat,
                         lon: cell_extent_record.extent_se_lon,
                     };
+
+                    extent_corners = [
+                        Position {
+                            lat: cell_extent_record.extent_sw_lat,
+                            lon: cell_extent_record.extent_sw_lon,
+                        },
+                        extent.top_left,
+                        Position {
+                            lat: cell_extent_record.extent_ne_lat,
+                            lon: cell_extent_record.extent_ne_lon,
+                        },
+                        extent.bottom_right,
+                    ];
                 }
 
                 CELL_COVR_RECORD => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
                 CELL_NOCOVR_RECORD => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
                 FEATURE_ID_RECORD => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
-                    assert_eq!(
-                        buf_size,
-                        std::mem::size_of::<OsencFeatureIdentificationRecordPayload>()
-                    );
+                    expect_payload_size(buf_size, OsencFeatureIdentificationRecordPayload::SIZE)?;
 
-                    let mut buf =
-                        [0u8; std::mem::size_of::<OsencFeatureIdentificationRecordPayload>()];
+                    let payload: OsencFeatureIdentificationRecordPayload = read_payload(reader)?;
 
-                    reader.read_exact(&mut buf)?;
-
-                    let payload: OsencFeatureIdentificationRecordPayload =
-                        unsafe { std::mem::transmute(buf) };
+                    let mut s57 = S57::from_type_code(payload.get_feature_type_code());
+                    s57.set_feature_id(payload.get_feature_id());
+                    s57.set_primitive(GeometryPrimitive::from_u8(payload.get_feature_primitive()));
+                    s57.set_file_offset(record_offset);
 
-                    s57_vector.push(S57::from_type_code(payload.get_feature_type_code()));
-                    current_s57 = s57_vector.last_mut();
+                    if let Some(finished) = current_s57.replace(s57) {
+                        if on_feature(finished).is_break() {
+                            stopped = true;
+                            break;
+                        }
+                    }
                 }
                 FEATURE_ATTRIBUTE_RECORD => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
-
-                    //assert_eq!(buf_size, std::mem::size_of::<OsencAttributeRecordPayload>());
+                    let buf_size = payload_size(&record_base)?;
 
                     let mut buf = vec![0u8; buf_size];
 
                     reader.read_exact(&mut buf)?;
 
-                    let payload = unsafe {
-                        // https://github.com/bdbcat/o-charts_pi/blob/e10fc5c3e9da31a1d19b264df1ac11e39d9226bb/src/Osenc.cpp#L1500
-                        // WARNING: Intentionally mimics buggy(?) C++ implementation
-                        // Original code inconsistently reads buffer of varying lengths (5-12 bytes)
-                        // into a fixed 11-byte struct, suggesting a potential memory handling bug
-                        // in the original C++ code that miraculously "worked"
-                        std::ptr::read_unaligned(buf.as_ptr() as *const OsencAttributeRecordPayload)
-                    };
+                    // https://github.com/bdbcat/o-charts_pi/blob/e10fc5c3e9da31a1d19b264df1ac11e39d9226bb/src/Osenc.cpp#L1500
+                    // the C++ implementation reads the varying record length (5-12 bytes)
+                    // into a fixed 11-byte struct, the value is decoded from the bytes
+                    // actually present instead
+                    if buf.len() < OsencAttributeRecordPayload::SIZE {
+                        return Err(ChartError::MalformedHeader);
+                    }
+                    let payload = OsencAttributeRecordPayload::from_le_bytes(&buf);
 
                     let attribute_value_type = payload.get_attribute_value_type();
+
                     let attribute = S57Attribute::from_type_code(payload.get_attribute_type_code());
 
                     if attribute == S57Attribute::Unknown {
+                        unknown_attributes += 1;
                         continue;
                     }
 
                     match attribute_value_type {
                         0 => {
                             if let Some(ref mut s57) = current_s57 {
-                                s57.set_attribute(
-                                    attribute,
-                                    s57::AttributeValue::UInt32(
-                                        payload.get_attribute_value().get_int(),
-                                    ),
-                                );
+                                let value = payload.get_attribute_value().get_int();
+
+                                // some encoders write float attributes as int
+                                if attribute.is_float() {
+                                    if options.diagnostics {
+                                        warnings.push(ParseWarning::ValueTypeMismatch {
+                                            attribute_code: payload.get_attribute_type_code(),
+                                            value_type: attribute_value_type,
+                                        });
+                                    }
+
+                                    s57.set_attribute(
+                                        attribute,
+                                        s57::AttributeValue::Double(value as f64),
+                                    );
+                                } else {
+                                    s57.set_attribute(
+                                        attribute,
+                                        s57::AttributeValue::UInt32(value),
+                                    );
+                                }
                             }
                         }
                         2 => {
@@ -366,19 +686,7 @@ impl ChartFile {
                         }
                         4 => {
                             if let Some(ref mut s57) = current_s57 {
-                                let char_ptr = buf.as_ptr() as *const c_char;
-
-                                let string_offset =
-                                    std::mem::size_of::<OsencAttributeRecordPayload>()
-                                        - std::mem::size_of::<*const c_char>();
-
-                                let c_str = unsafe {
-                                    CStr::from_ptr(
-                                        (char_ptr as *const u8).add(string_offset) as *const c_char
-                                    )
-                                };
-
-                                if let Ok(str) = c_str.to_str() {
+                                if let Some(str) = payload.get_attribute_value().get_str() {
                                     s57.set_attribute(
                                         attribute,
                                         s57::AttributeValue::String(str.to_string()),
@@ -386,42 +694,35 @@ impl ChartFile {
                                 }
                             }
                         }
-                        _ => {}
+                        _ => {
+                            if options.diagnostics {
+                                warnings.push(ParseWarning::UnhandledValueType {
+                                    attribute_code: payload.get_attribute_type_code(),
+                                    value_type: attribute_value_type,
+                                });
+                            }
+                        }
                     }
                 }
                 FEATURE_GEOMETRY_RECORD_POINT => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
-
-                    assert_eq!(
-                        buf_size,
-                        std::mem::size_of::<OsencPointGeometryRecordPayload>()
-                    );
-
-                    let mut buf = [0u8; std::mem::size_of::<OsencPointGeometryRecordPayload>()];
+                    let buf_size = payload_size(&record_base)?;
 
-                    reader.read_exact(&mut buf)?;
+                    expect_payload_size(buf_size, OsencPointGeometryRecordPayload::SIZE)?;
 
-                    let point: OsencPointGeometryRecordPayload =
-                        unsafe { std::mem::transmute(buf) };
+                    let point: OsencPointGeometryRecordPayload = read_payload(reader)?;
                     if let Some(ref mut s57) = current_s57 {
                         s57.set_point_geometry(point.into());
                     }
                 }
                 FEATURE_GEOMETRY_RECORD_AREA => {
-                    let payload_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let payload_size = payload_size(&record_base)?;
                     let mut payload_buffer = vec![0u8; payload_size];
                     reader.read_exact(&mut payload_buffer)?;
 
                     let mut cursor = std::io::Cursor::new(&payload_buffer);
 
-                    let mut record_buf =
-                        [0u8; std::mem::size_of::<OsencAreaGeometryRecordPayload>()];
-                    cursor.read_exact(&mut record_buf)?;
-
                     let record: OsencAreaGeometryRecordPayload =
-                        unsafe { std::mem::transmute(record_buf) };
+                        read_payload(&mut cursor).map_err(truncated)?;
 
                     // skip tesselation data
                     let triprim_count = record.get_triprim_count();
@@ -434,108 +735,81 @@ impl ChartFile {
                     for _ in 0..triprim_count {
                         cursor.seek(SeekFrom::Current(1))?;
 
-                        let mut data_nvert = [0u8; std::mem::size_of::<u32>()];
-                        cursor.read_exact(&mut data_nvert)?;
-
-                        let nvert: u32 = unsafe { std::mem::transmute(data_nvert) };
+                        let nvert = read_u32(&mut cursor).map_err(truncated)?;
                         let byte_size = nvert as i64 * 2 * std::mem::size_of::<f32>() as i64;
 
                         cursor.seek(SeekFrom::Current(4 * std::mem::size_of::<f64>() as i64))?;
                         cursor.seek(SeekFrom::Current(byte_size))?;
                     }
 
-                    let remaining_size = payload_size - cursor.position() as usize;
-
-                    assert_eq!(remaining_size % std::mem::size_of::<LineElement>(), 0);
+                    let line_data = payload_buffer
+                        .get(cursor.position() as usize..)
+                        .filter(|data| data.len() % LineElement::SIZE == 0)
+                        .ok_or(ChartError::MalformedHeader)?;
 
-                    let mut line_data: Vec<u8> = vec![0u8; remaining_size];
-                    cursor.read_exact(&mut line_data)?;
-
-                    let ptr = line_data.as_mut_ptr() as *mut LineElement;
-                    let len = line_data.len() / std::mem::size_of::<LineElement>();
-
-                    std::mem::forget(line_data);
-
-                    let lines: Vec<LineElement> = unsafe { Vec::from_raw_parts(ptr, len, len) };
+                    let lines: Vec<LineElement> = line_data
+                        .chunks_exact(LineElement::SIZE)
+                        .map(LineElement::from_le_bytes)
+                        .collect();
                     if let Some(ref mut s57) = current_s57 {
                         s57.set_polygon_geometry(&lines);
+                        s57.set_extent(record.get_extent());
                     }
                 }
 
                 FEATURE_GEOMETRY_RECORD_AREA_EXT => {
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
                 FEATURE_GEOMETRY_RECORD_LINE => {
-                    let payload_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let payload_size = payload_size(&record_base)?;
                     let mut payload_buffer = vec![0u8; payload_size];
 
                     reader.read_exact(&mut payload_buffer)?;
 
                     let mut cursor = std::io::Cursor::new(&payload_buffer);
 
-                    cursor.seek(SeekFrom::Current(
-                        std::mem::size_of::<OsencLineGeometryRecordPayload>() as i64,
-                    ))?;
-
-                    assert_eq!(
-                        (payload_size - cursor.position() as usize)
-                            % std::mem::size_of::<LineElement>(),
-                        0
-                    );
-
-                    let mut line_data = vec![0u8; payload_size - cursor.position() as usize];
-                    cursor.read_exact(&mut line_data)?;
-
-                    let ptr = line_data.as_mut_ptr() as *mut LineElement;
-                    let len = line_data.len() / std::mem::size_of::<LineElement>();
+                    let record: OsencLineGeometryRecordPayload =
+                        read_payload(&mut cursor).map_err(truncated)?;
 
-                    std::mem::forget(line_data);
+                    let line_data = payload_buffer
+                        .get(cursor.position() as usize..)
+                        .filter(|data| data.len() % LineElement::SIZE == 0)
+                        .ok_or(ChartError::MalformedHeader)?;
 
-                    let lines: Vec<LineElement> = unsafe { Vec::from_raw_parts(ptr, len, len) };
+                    let lines: Vec<LineElement> = line_data
+                        .chunks_exact(LineElement::SIZE)
+                        .map(LineElement::from_le_bytes)
+                        .collect();
                     if let Some(ref mut s57) = current_s57 {
                         s57.set_line_geometry(&lines);
+                        s57.set_extent(record.get_extent());
                     }
                 }
                 FEATURE_GEOMETRY_RECORD_MULTIPOINT => {
-                    let payload_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let payload_size = payload_size(&record_base)?;
                     let mut payload_buffer = vec![0u8; payload_size];
 
                     reader.read_exact(&mut payload_buffer)?;
 
                     let mut cursor = std::io::Cursor::new(&payload_buffer);
 
-                    let mut record_data =
-                        [0u8; std::mem::size_of::<OsencMultipointGeometryRecordPayload>()];
-                    cursor.read_exact(&mut record_data)?;
-
                     let record: OsencMultipointGeometryRecordPayload =
-                        unsafe { std::mem::transmute(record_data) };
+                        read_payload(&mut cursor).map_err(truncated)?;
 
                     let mut multipoint_geometry: Vec<PointGeometry> = Vec::new();
+                    let reference = options
+                        .projection_reference
+                        .unwrap_or_else(|| extent.center());
 
-                    let mut point_data =
-                        vec![0u8; std::mem::size_of::<f32>() * record.point_count as usize];
-                    cursor.read_exact(&mut point_data)?;
-
-                    let ptr = point_data.as_mut_ptr() as *mut f32;
-                    let len = record.point_count as usize;
-
-                    std::mem::forget(point_data);
-
-                    let points: Vec<f32> = unsafe { Vec::from_raw_parts(ptr, len * 3, len) };
+                    for _ in 0..record.point_count {
+                        let easting = read_f32(&mut cursor).map_err(truncated)? as f64;
+                        let northing = read_f32(&mut cursor).map_err(truncated)? as f64;
+                        let depth = read_f32(&mut cursor).map_err(truncated)? as f64;
 
-                    for i in 0..len {
-                        let easting = points[i * 3 + 0] as f64;
-                        let northing = points[i * 3 + 1] as f64;
-                        let depth = points[i * 3 + 2] as f64;
-
-                        let pos =
-                            Position::from_simple_mercator(easting, northing, &extent.center());
+                        let pos = Position::from_simple_mercator(easting, northing, &reference);
 
                         multipoint_geometry.push(PointGeometry {
                             position: pos,
@@ -545,61 +819,2150 @@ impl ChartFile {
 
                     if let Some(ref mut s57) = current_s57 {
                         s57.set_multi_point_geometry(multipoint_geometry);
+                        s57.set_extent(record.get_extent());
                     }
                 }
                 VECTOR_EDGE_NODE_TABLE_RECORD => {
-                    // not needed for my data
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let payload_size = payload_size(&record_base)?;
+                    let mut payload_buffer = vec![0u8; payload_size];
 
-                    reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    reader.read_exact(&mut payload_buffer)?;
+
+                    let mut cursor = std::io::Cursor::new(&payload_buffer);
+                    let reference = options
+                        .projection_reference
+                        .unwrap_or_else(|| extent.center());
+
+                    let edge_count = read_u32(&mut cursor).map_err(truncated)?;
+
+                    for _ in 0..edge_count {
+                        let index = read_u32(&mut cursor).map_err(truncated)?;
+                        let point_count = read_u32(&mut cursor).map_err(truncated)?;
+
+                        // the count comes from the file, don't trust it for the allocation
+                        let mut positions =
+                            Vec::with_capacity((point_count as usize).min(payload_size / 8));
+                        for _ in 0..point_count {
+                            let easting = read_f32(&mut cursor).map_err(truncated)? as f64;
+                            let northing = read_f32(&mut cursor).map_err(truncated)? as f64;
+
+                            positions.push(Position::from_simple_mercator(
+                                easting, northing, &reference,
+                            ));
+                        }
+
+                        let mut edge = VectorEdge::new();
+                        edge.set_positions(positions);
+                        vector_edges.insert(index, edge);
+                    }
                 }
 
                 VECTOR_EDGE_NODE_TABLE_EXT_RECORD => {
                     // not needed for my data
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
                 VECTOR_CONNECTED_NODE_TABLE_RECORD => {
-                    // not needed for my data
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let payload_size = payload_size(&record_base)?;
+                    let mut payload_buffer = vec![0u8; payload_size];
 
-                    reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    reader.read_exact(&mut payload_buffer)?;
+
+                    let mut cursor = std::io::Cursor::new(&payload_buffer);
+                    let reference = options
+                        .projection_reference
+                        .unwrap_or_else(|| extent.center());
+
+                    let node_count = read_u32(&mut cursor).map_err(truncated)?;
+
+                    for _ in 0..node_count {
+                        let index = read_u32(&mut cursor).map_err(truncated)?;
+                        let easting = read_f32(&mut cursor).map_err(truncated)? as f64;
+                        let northing = read_f32(&mut cursor).map_err(truncated)? as f64;
+
+                        connected_nodes.insert(
+                            index,
+                            ConnectedNode::new(Position::from_simple_mercator(
+                                easting, northing, &reference,
+                            )),
+                        );
+                    }
                 }
 
                 VECTOR_CONNECTED_NODE_TABLE_EXT_RECORD => {
                     // not needed for my data
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
                 CELL_TXTDSC_INFO_FILE_RECORD => {
                     // not needed for my data
-                    let buf_size = record_base.get_record_len() as usize
-                        - std::mem::size_of::<OsencRecordBase>();
+                    let buf_size = payload_size(&record_base)?;
 
                     reader.seek(SeekFrom::Current(buf_size as i64))?;
+                    *skipped_records.entry(record_type).or_default() += 1;
                 }
                 _ => {
+                    *skipped_records.entry(record_type).or_default() += 1;
                     break;
                 }
             }
         }
 
+        if let Some(finished) = current_s57.take().filter(|_| !stopped) {
+            let _ = on_feature(finished);
+        }
+
         Ok(ChartFile {
             extent,
-            s57: s57_vector,
+            extent_corners,
+            s57: Vec::new(),
+            vector_edges,
+            connected_nodes,
             name,
             publishdate,
             edition,
             updatedate,
             update,
+            applied_updates,
             nativescale,
             soundingdatum,
+            subscription_status,
+            status,
+            report: ParseReport {
+                skipped_records,
+                orphaned_records,
+                unknown_attributes,
+                warnings: warnings.len(),
+            },
+            warnings,
+            parsed_at: options.measure_timing.then(SystemTime::now),
+            parse_duration: options.measure_timing.then(|| parse_start.elapsed()),
         })
     }
 }
+
+/// Payload length of a record, its length minus the record header
+fn payload_size(record_base: &OsencRecordBase) -> Result<usize, ChartError> {
+    (record_base.get_record_len() as usize)
+        .checked_sub(OsencRecordBase::SIZE)
+        .ok_or(ChartError::MalformedHeader)
+}
+
+/// Reads past the end of a record's payload buffer
+fn truncated(_: std::io::Error) -> ChartError {
+    ChartError::MalformedHeader
+}
+
+fn expect_payload_size(payload_size: usize, expected: usize) -> Result<(), ChartError> {
+    if payload_size == expected {
+        Ok(())
+    } else {
+        Err(ChartError::MalformedHeader)
+    }
+}
+
+fn read_payload<T: FromLeBytes, R: Read>(reader: &mut R) -> std::io::Result<T> {
+    let mut buf = vec![0u8; T::SIZE];
+    reader.read_exact(&mut buf)?;
+    Ok(T::from_le_bytes(&buf))
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> std::io::Result<u16> {
+    let mut buf = [0u8; std::mem::size_of::<u16>()];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+    let mut buf = [0u8; std::mem::size_of::<u32>()];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(reader: &mut R) -> std::io::Result<f32> {
+    let mut buf = [0u8; std::mem::size_of::<f32>()];
+    reader.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+/// Parses `reader` and calls `f` with each feature as soon as all of its
+/// records are read, without keeping the features in memory. Stops reading
+/// when `f` returns `Break`. The vector tables sit at the end of the file,
+/// so they are read in a first pass and line and area geometry is built
+/// before a feature is handed to `f`.
+#[allow(dead_code)]
+pub fn stream_features<R: Read + Seek, F: FnMut(S57) -> ControlFlow<()>>(
+    reader: &mut R,
+    options: &ParseOptions,
+    mut f: F,
+) -> Result<(), ChartError> {
+    let start = reader.stream_position()?;
+
+    let table_options = ParseOptions {
+        record_filter: Some(HashSet::from([
+            VECTOR_EDGE_NODE_TABLE_RECORD,
+            VECTOR_CONNECTED_NODE_TABLE_RECORD,
+        ])),
+        diagnostics: false,
+        record_offsets: false,
+        measure_timing: false,
+        ..options.clone()
+    };
+    let tables = ChartFile::parse_records(reader, &table_options, |_| ControlFlow::Continue(()))?;
+
+    reader.seek(SeekFrom::Start(start))?;
+    ChartFile::parse_records(reader, options, |mut s57| {
+        s57.build_geometry(&tables.vector_edges, &tables.connected_nodes);
+        f(s57)
+    })
+    .map(|_| ())
+}
+
+#[allow(dead_code)]
+impl ChartFile {
+    pub fn extent(&self) -> &Rect {
+        &self.extent
+    }
+
+    /// Cell outline as the closed SW, NW, NE, SE, SW ring. Unlike `extent`
+    /// this keeps the corners as encoded, which need not form a rectangle.
+    pub fn extent_polygon(&self) -> MultiGeometry {
+        let mut ring = self.extent_corners.to_vec();
+        ring.push(self.extent_corners[0]);
+        ring
+    }
+
+    /// Features in the order they appear in the file. Exporters rely on
+    /// this order being preserved.
+    pub fn features(&self) -> &[S57] {
+        &self.s57
+    }
+
+    /// Cell name, e.g. "OC-49-M11SO4"
+    pub fn name(&self) -> &str {
+        self.name.trim_end_matches('\0')
+    }
+
+    pub fn publish_date(&self) -> &str {
+        self.publishdate.trim_end_matches('\0')
+    }
+
+    pub fn edition(&self) -> u16 {
+        self.edition
+    }
+
+    pub fn update_date(&self) -> &str {
+        self.updatedate.trim_end_matches('\0')
+    }
+
+    pub fn update(&self) -> u16 {
+        self.update
+    }
+
+    /// Update numbers of the HEADER_CELL_UPDATE records in the file, in the
+    /// order they were read. Update 0, the base edition, is not listed.
+    pub fn applied_updates(&self) -> &[u16] {
+        &self.applied_updates
+    }
+
+    /// Compilation scale of the cell, the denominator of 1:n
+    pub fn native_scale(&self) -> u32 {
+        self.nativescale
+    }
+
+    /// Features ordered by object class code, then feature id
+    pub fn sorted_features(&self) -> Vec<&S57> {
+        let mut features: Vec<&S57> = self.s57.iter().collect();
+        features.sort_by_key(|s57| (s57.s57_type() as u16, s57.feature_id()));
+        features
+    }
+
+    /// Wall clock time the chart was parsed, see `ParseOptions::measure_timing`
+    pub fn parsed_at(&self) -> Option<SystemTime> {
+        self.parsed_at
+    }
+
+    /// Time spent in `parse_file`, see `ParseOptions::measure_timing`
+    pub fn parse_duration(&self) -> Option<Duration> {
+        self.parse_duration
+    }
+
+    /// Subscription state from the server status record, if the file has one
+    pub fn subscription_status(&self) -> Option<SubscriptionStatus> {
+        self.subscription_status
+    }
+
+    /// Days until the subscription expires and the grace period, e.g. to
+    /// warn before a chart stops loading
+    pub fn status(&self) -> &ChartStatus {
+        &self.status
+    }
+
+    pub fn report(&self) -> &ParseReport {
+        &self.report
+    }
+
+    /// Warnings collected while parsing with [`ParseOptions::diagnostics`]
+    pub fn warnings(&self) -> &Vec<ParseWarning> {
+        &self.warnings
+    }
+
+    pub fn version_info(&self) -> ChartVersion {
+        ChartVersion {
+            edition: self.edition,
+            update: self.update,
+            updatedate: self.updatedate.clone(),
+            publishdate: self.publishdate.clone(),
+        }
+    }
+
+    /// Features whose OBJNAM or NOBJNM contains `query`, ignoring case,
+    /// accents and repeated whitespace
+    pub fn search_by_name(&self, query: &str) -> Vec<&S57> {
+        let query = normalize_name(query);
+
+        self.s57
+            .iter()
+            .filter(|s57| {
+                [S57Attribute::OBJNAM, S57Attribute::NOBJNM]
+                    .into_iter()
+                    .filter_map(|attribute| s57.attribute(attribute))
+                    .filter_map(|value| value.as_str())
+                    .any(|name| normalize_name(name).contains(&query))
+            })
+            .collect()
+    }
+
+    /// Features whose WATLEV makes them appear or disappear with the tide
+    pub fn intertidal_features(&self) -> Vec<&S57> {
+        self.s57
+            .iter()
+            .filter(|s57| s57.water_level().is_some_and(|level| level.is_tidal()))
+            .collect()
+    }
+
+    /// Number of features per object class
+    pub fn type_histogram(&self) -> HashMap<S57Type, usize> {
+        let mut histogram: HashMap<S57Type, usize> = HashMap::new();
+
+        for s57 in &self.s57 {
+            *histogram.entry(s57.s57_type()).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    /// JSON object mapping each present object class acronym to
+    /// `{ "count", "vertex_count", "has_names" }`
+    pub fn layer_stats_json(&self) -> String {
+        let mut layers: BTreeMap<String, (usize, usize, bool)> = BTreeMap::new();
+
+        for (s57_type, count) in self.type_histogram() {
+            layers.insert(s57_type.acronym().to_string(), (count, 0, false));
+        }
+
+        for s57 in &self.s57 {
+            if let Some(layer) = layers.get_mut(s57.s57_type().acronym()) {
+                layer.1 += s57.vertex_count();
+                layer.2 |= s57.attribute(S57Attribute::OBJNAM).is_some()
+                    || s57.attribute(S57Attribute::NOBJNM).is_some();
+            }
+        }
+
+        let entries: Vec<String> = layers
+            .iter()
+            .map(|(acronym, (count, vertex_count, has_names))| {
+                format!(
+                    "\"{}\":{{\"count\":{},\"vertex_count\":{},\"has_names\":{}}}",
+                    acronym, count, vertex_count, has_names
+                )
+            })
+            .collect();
+
+        format!("{{{}}}", entries.join(","))
+    }
+
+    /// Every feature with a point geometry paired with its position
+    pub fn point_layer(&self) -> Vec<(&S57, Position)> {
+        self.s57
+            .iter()
+            .filter_map(|s57| s57.point_geometry().map(|position| (s57, *position)))
+            .collect()
+    }
+
+    /// [`ChartFile::point_layer`] plus one entry per sounding of multipoint features
+    pub fn point_layer_with_soundings(&self) -> Vec<(&S57, Position)> {
+        let mut layer = self.point_layer();
+
+        for s57 in &self.s57 {
+            layer.extend(
+                s57.multi_point_geometry()
+                    .iter()
+                    .map(|point| (s57, point.position)),
+            );
+        }
+
+        layer
+    }
+
+    /// All distinct colours referenced by COLOUR attributes
+    pub fn colours_used(&self) -> BTreeSet<S52Colour> {
+        self.s57.iter().flat_map(|s57| s57.colours()).collect()
+    }
+
+    /// Feature centroids binned into a `grid_rows` x `grid_cols` grid over the
+    /// chart extent, indexed `[row][col]` with row 0 at the north edge.
+    /// Centroids outside the extent count towards the nearest edge cell.
+    pub fn feature_density(&self, grid_cols: usize, grid_rows: usize) -> Vec<Vec<usize>> {
+        let mut grid = vec![vec![0usize; grid_cols]; grid_rows];
+
+        if grid_cols == 0 || grid_rows == 0 {
+            return grid;
+        }
+
+        let width = self.extent.bottom_right.lon - self.extent.top_left.lon;
+        let height = self.extent.top_left.lat - self.extent.bottom_right.lat;
+
+        for centroid in self.s57.iter().filter_map(|s57| s57.centroid()) {
+            let x = (centroid.lon - self.extent.top_left.lon) / width;
+            let y = (self.extent.top_left.lat - centroid.lat) / height;
+
+            let col = ((x * grid_cols as f64).max(0.0) as usize).min(grid_cols - 1);
+            let row = ((y * grid_rows as f64).max(0.0) as usize).min(grid_rows - 1);
+
+            grid[row][col] += 1;
+        }
+
+        grid
+    }
+
+    /// Index pairs of features with identical content, see [`S57::same_content`].
+    /// Features are bucketed by type, vertex count and first vertex, only
+    /// features within a bucket are compared.
+    pub fn find_duplicates(&self) -> Vec<(usize, usize)> {
+        let mut buckets: HashMap<_, Vec<usize>> = HashMap::new();
+
+        for (index, s57) in self.s57.iter().enumerate() {
+            // + 0.0 folds -0.0 into 0.0, which compare equal
+            let first = s57
+                .positions()
+                .next()
+                .map(|p| ((p.lat + 0.0).to_bits(), (p.lon + 0.0).to_bits()));
+            let key = (s57.s57_type(), s57.positions().count(), first);
+            buckets.entry(key).or_default().push(index);
+        }
+
+        let mut duplicates = Vec::new();
+
+        for indices in buckets.values() {
+            for (n, &i) in indices.iter().enumerate() {
+                for &j in &indices[n + 1..] {
+                    if self.s57[i].same_content(&self.s57[j]) {
+                        duplicates.push((i, j));
+                    }
+                }
+            }
+        }
+
+        duplicates.sort_unstable();
+        duplicates
+    }
+
+    /// Attributes present on every feature of `features`
+    pub fn common_attributes(&self, features: &[&S57]) -> BTreeSet<S57Attribute> {
+        let Some((first, rest)) = features.split_first() else {
+            return BTreeSet::new();
+        };
+
+        let mut common: BTreeSet<S57Attribute> = first.attribute_list().into_iter().collect();
+
+        for s57 in rest {
+            common.retain(|attribute| s57.attribute(*attribute).is_some());
+        }
+
+        common
+    }
+
+    /// Moves the connected nodes that line features start or end their
+    /// edges at onto the first such node seen within `tolerance_m` metres,
+    /// so touching lines share their end points. Line and area geometry is
+    /// rebuilt from the moved nodes.
+    pub fn snap_topology(&mut self, tolerance_m: f64) {
+        let mut visited: HashSet<u32> = HashSet::new();
+        let mut anchors: Vec<Position> = Vec::new();
+        let mut snapped = false;
+
+        for s57 in &self.s57 {
+            for element in s57.line_elements() {
+                for index in [element.start_connected_node(), element.end_connected_node()] {
+                    if !visited.insert(index) {
+                        continue;
+                    }
+                    let Some(node) = self.connected_nodes.get_mut(&index) else {
+                        continue;
+                    };
+
+                    match anchors
+                        .iter()
+                        .find(|anchor| anchor.distance_to(node.position()) <= tolerance_m)
+                    {
+                        Some(anchor) => {
+                            if anchor != node.position() {
+                                node.set_position(*anchor);
+                                snapped = true;
+                            }
+                        }
+                        None => anchors.push(*node.position()),
+                    }
+                }
+            }
+        }
+
+        if snapped {
+            self.build_geometries();
+        }
+    }
+
+    /// (Re)builds line and area geometry of all features from the vector
+    /// tables. Returns the unresolved references by index into `features()`.
+    pub fn build_geometries(&mut self) -> Vec<(usize, GeometryError)> {
+        let mut unresolved = Vec::new();
+
+        for (index, s57) in self.s57.iter_mut().enumerate() {
+            unresolved.extend(
+                s57.build_geometry(&self.vector_edges, &self.connected_nodes)
+                    .into_iter()
+                    .map(|error| (index, error)),
+            );
+        }
+
+        unresolved
+    }
+
+    /// Connected nodes by index, shared end points of vector edges
+    pub fn connected_nodes(&self) -> &HashMap<u32, ConnectedNode> {
+        &self.connected_nodes
+    }
+
+    /// Moves connected nodes closer than `tolerance_m` metres onto one
+    /// position, so edges of adjacent features meet exactly. Nodes are
+    /// visited by index, the first node of a cluster keeps its position.
+    /// Line and area geometry is rebuilt from the moved nodes.
+    pub fn merge_coincident_nodes(&mut self, tolerance_m: f64) {
+        let mut nodes: Vec<(&u32, &mut ConnectedNode)> = self.connected_nodes.iter_mut().collect();
+        nodes.sort_by_key(|(index, _)| **index);
+
+        let mut anchors: Vec<Position> = Vec::new();
+        let mut merged = false;
+
+        for (_, node) in nodes {
+            match anchors
+                .iter()
+                .find(|anchor| anchor.distance_to(node.position()) <= tolerance_m)
+            {
+                Some(anchor) => {
+                    if anchor != node.position() {
+                        node.set_position(*anchor);
+                        merged = true;
+                    }
+                }
+                None => anchors.push(*node.position()),
+            }
+        }
+
+        if merged {
+            self.build_geometries();
+        }
+    }
+
+    /// Features grouped into draw layers by display priority, lowest first.
+    /// Meta and collection objects (priority 0) are left out.
+    pub fn features_by_priority(&self) -> Vec<(u8, Vec<&S57>)> {
+        let mut layers: BTreeMap<u8, Vec<&S57>> = BTreeMap::new();
+
+        for s57 in &self.s57 {
+            let priority = s57.display_priority();
+            if priority > 0 {
+                layers.entry(priority).or_default().push(s57);
+            }
+        }
+
+        layers.into_iter().collect()
+    }
+
+    /// Attributes stored with a value type their catalogue entry doesn't
+    /// allow, e.g. free text where an enumerated code is expected
+    pub fn validate_attributes(&self) -> Vec<AttributeValidationError> {
+        let mut errors = Vec::new();
+
+        for (feature_index, s57) in self.s57.iter().enumerate() {
+            let mut attributes = s57.attribute_list();
+            attributes.sort();
+
+            for attribute in attributes {
+                let Some(value) = s57.attribute(attribute) else {
+                    continue;
+                };
+
+                let expected = attribute.kind();
+                if !expected.accepts(value) {
+                    errors.push(AttributeValidationError {
+                        feature_index,
+                        attribute,
+                        expected,
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Features whose populated geometry doesn't match their declared
+    /// primitive, by index into `features()`. Features with an unknown
+    /// primitive (e.g. meta objects) are not checked.
+    pub fn validate_primitives(&self) -> Vec<(usize, PrimitiveMismatch)> {
+        let primitives = [
+            GeometryPrimitive::Point,
+            GeometryPrimitive::Multipoint,
+            GeometryPrimitive::Line,
+            GeometryPrimitive::Area,
+        ];
+        let mut mismatches = Vec::new();
+
+        for (index, s57) in self.s57.iter().enumerate() {
+            let declared = s57.primitive();
+            if declared == GeometryPrimitive::Unknown {
+                continue;
+            }
+
+            if !primitives
+                .into_iter()
+                .any(|found| declared.accepts(found) && s57.has_geometry(found))
+            {
+                mismatches.push((index, PrimitiveMismatch::Missing(declared)));
+            }
+
+            for found in primitives {
+                if !declared.accepts(found) && s57.has_geometry(found) {
+                    mismatches.push((index, PrimitiveMismatch::Unexpected { declared, found }));
+                }
+            }
+        }
+
+        mismatches
+    }
+
+    /// (land, water) area in square metres, summed over LNDARE and DEPARE
+    /// features. `None` if the cell has neither.
+    pub fn land_water_ratio(&self) -> Option<(f64, f64)> {
+        let total = |s57_type: S57Type| {
+            let mut areas = self
+                .s57
+                .iter()
+                .filter(|s57| s57.s57_type() == s57_type)
+                .map(|s57| s57.area())
+                .peekable();
+
+            areas.peek().is_some().then(|| areas.sum::<f64>())
+        };
+
+        let land = total(S57Type::LNDARE);
+        let water = total(S57Type::DEPARE);
+
+        if land.is_none() && water.is_none() {
+            return None;
+        }
+
+        Some((land.unwrap_or(0.0), water.unwrap_or(0.0)))
+    }
+
+    /// Triangle meshes of all area features as `[lon, lat]` vertices and
+    /// indices, ready for upload to a GPU. Each polygon of a feature is
+    /// triangulated on its own and the meshes are merged per feature.
+    /// The tessellation embedded in area records isn't parsed yet, so
+    /// every mesh is built by ear clipping.
+    #[allow(clippy::type_complexity)]
+    pub fn triangulate_areas(&self) -> Vec<(&S57, Vec<[f64; 2]>, Vec<u32>)> {
+        self.s57
+            .iter()
+            .filter_map(|s57| {
+                let mut vertices = Vec::new();
+                let mut indices = Vec::new();
+
+                for polygon in s57.structured_polygons() {
+                    let (polygon_vertices, polygon_indices) = polygon.triangulate();
+                    let offset = vertices.len() as u32;
+                    vertices.extend(polygon_vertices);
+                    indices.extend(polygon_indices.into_iter().map(|index| index + offset));
+                }
+
+                (!indices.is_empty()).then_some((s57, vertices, indices))
+            })
+            .collect()
+    }
+
+    /// Features whose TXTDSC or NTXTDS names `filename`, ignoring case
+    pub fn features_referencing_text(&self, filename: &str) -> Vec<&S57> {
+        let filename = filename.trim();
+
+        self.s57
+            .iter()
+            .filter(|s57| {
+                [
+                    s57.text_description_file(),
+                    s57.national_text_description_file(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|file| file.eq_ignore_ascii_case(filename))
+            })
+            .collect()
+    }
+
+    pub fn summary(&self) -> ChartSummary {
+        let north = self.extent.top_left.lat;
+        let west = self.extent.top_left.lon;
+        let south = self.extent.bottom_right.lat;
+        let east = self.extent.bottom_right.lon;
+
+        ChartSummary {
+            name: self.name.trim_end_matches('\0').to_string(),
+            scale: self.nativescale,
+            edition: self.edition,
+            update: self.update,
+            extent_corners: [[west, north], [east, north], [east, south], [west, south]],
+            feature_counts: self
+                .type_histogram()
+                .into_iter()
+                .map(|(s57_type, count)| (s57_type.acronym().to_string(), count))
+                .collect(),
+            subscription_status: self.subscription_status,
+        }
+    }
+
+    /// Features without any attribute, often an encoding problem
+    pub fn attributeless_features(&self) -> Vec<&S57> {
+        self.s57
+            .iter()
+            .filter(|s57| s57.attribute_list().is_empty())
+            .collect()
+    }
+
+    /// (width, height) in pixels of the extent drawn in mercator at
+    /// 1:`scale` and `dpi`. The scale is true at the extent's mid latitude.
+    pub fn pixel_dimensions(&self, scale: u32, dpi: f64) -> (u32, u32) {
+        let north = self.extent.top_left.lat * s57::DEGREE;
+        let south = self.extent.bottom_right.lat * s57::DEGREE;
+        let lon_span = (self.extent.bottom_right.lon - self.extent.top_left.lon) * s57::DEGREE;
+
+        let mercator_y = |lat: f64| (std::f64::consts::FRAC_PI_4 + lat / 2.0).tan().ln();
+
+        // projected metres on the unit mercator, shrunk to true length at mid latitude
+        let mid_scale = ((north + south) / 2.0).cos() * s57::WGS84_SEMIMAJOR_AXIS_METERS;
+        let width_m = lon_span.abs() * mid_scale;
+        let height_m = (mercator_y(north) - mercator_y(south)).abs() * mid_scale;
+
+        let pixels_per_metre = dpi / 0.0254 / scale.max(1) as f64;
+
+        (
+            (width_m * pixels_per_metre).round() as u32,
+            (height_m * pixels_per_metre).round() as u32,
+        )
+    }
+
+    /// M_SREL meta-features, see `S57::survey_reliability`
+    pub fn survey_reliability_zones(&self) -> Vec<&S57> {
+        self.s57
+            .iter()
+            .filter(|s57| s57.s57_type() == S57Type::M_SREL)
+            .collect()
+    }
+
+    /// Features with a vertex within `tolerance_m` metres of the extent
+    /// boundary (or outside it), typically clipped at the cell edge
+    pub fn boundary_features(&self, tolerance_m: f64) -> Vec<&S57> {
+        self.s57
+            .iter()
+            .filter(|s57| {
+                s57.positions().any(|position| {
+                    !self.extent.contains(position)
+                        || self.extent.distance_to_boundary(position) <= tolerance_m
+                })
+            })
+            .collect()
+    }
+
+    /// Distinct VALDCO values of all DEPCNT features, shallowest first
+    pub fn depth_contours(&self) -> Vec<f64> {
+        let mut contours: Vec<f64> = self
+            .s57
+            .iter()
+            .filter(|s57| s57.s57_type() == S57Type::DEPCNT)
+            .filter_map(|s57| s57.attribute(S57Attribute::VALDCO))
+            .filter_map(|value| value.as_f64())
+            .collect();
+
+        contours.sort_by(f64::total_cmp);
+        contours.dedup();
+        contours
+    }
+
+    /// Copy of the chart containing only the features matching `pred`
+    pub fn filter_features<F: Fn(&S57) -> bool>(&self, pred: F) -> ChartFile {
+        ChartFile {
+            extent: self.extent,
+            extent_corners: self.extent_corners,
+            s57: self.s57.iter().filter(|s57| pred(s57)).cloned().collect(),
+            vector_edges: self.vector_edges.clone(),
+            connected_nodes: self.connected_nodes.clone(),
+            name: self.name.clone(),
+            publishdate: self.publishdate.clone(),
+            edition: self.edition,
+            updatedate: self.updatedate.clone(),
+            update: self.update,
+            applied_updates: self.applied_updates.clone(),
+            nativescale: self.nativescale,
+            soundingdatum: self.soundingdatum.clone(),
+            warnings: self.warnings.clone(),
+            subscription_status: self.subscription_status,
+            status: self.status,
+            parsed_at: self.parsed_at,
+            report: self.report.clone(),
+            parse_duration: self.parse_duration,
+        }
+    }
+
+    /// Spatial index over the soundings of all SOUNDG features
+    pub fn sounding_index(&self) -> SoundingIndex {
+        SoundingIndex::new(
+            self.s57
+                .iter()
+                .filter(|s57| s57.s57_type() == S57Type::SOUNDG)
+                .flat_map(|s57| s57.multi_point_geometry()),
+        )
+    }
+
+    /// Soundings thinned so no two are closer than `min_spacing_m` metres.
+    /// Shoaler soundings are kept first, as they matter most for safety.
+    pub fn thinned_soundings(&self, min_spacing_m: f64) -> Vec<(Position, f64)> {
+        let mut soundings: Vec<&PointGeometry> = self
+            .s57
+            .iter()
+            .filter(|s57| s57.s57_type() == S57Type::SOUNDG)
+            .flat_map(|s57| s57.multi_point_geometry())
+            .collect();
+        soundings.sort_by(|a, b| a.value.total_cmp(&b.value));
+
+        let mut kept = SoundingIndex::new([]);
+        let mut thinned = Vec::new();
+
+        for sounding in soundings {
+            if kept
+                .query_min_depth(&sounding.position, min_spacing_m)
+                .is_none()
+            {
+                kept.insert(sounding.clone());
+                thinned.push((sounding.position, sounding.value));
+            }
+        }
+
+        thinned
+    }
+
+    /// Footprint of the actual data, tighter than `extent`
+    pub fn convex_hull(&self) -> MultiGeometry {
+        geometry::convex_hull(
+            self.s57
+                .iter()
+                .flat_map(|s57| s57.positions())
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Mean of the feature centroids weighted by their vertex counts, so it
+    /// lies where the content is dense. `None` if there is no geometry.
+    pub fn feature_center_of_mass(&self) -> Option<Position> {
+        let (weight, lat, lon) = self
+            .s57
+            .iter()
+            .filter_map(|s57| Some((s57.vertex_count() as f64, s57.centroid()?)))
+            .fold((0.0, 0.0, 0.0), |(weight, lat, lon), (count, centroid)| {
+                (
+                    weight + count,
+                    lat + centroid.lat * count,
+                    lon + centroid.lon * count,
+                )
+            });
+
+        if weight == 0.0 {
+            return None;
+        }
+
+        Some(Position {
+            lat: lat / weight,
+            lon: lon / weight,
+        })
+    }
+
+    /// Indices into `features()` of features with built geometry outside
+    /// the extent declared by their geometry record, which points at a
+    /// projection or parse error. Allows for the f32 precision of the
+    /// vector tables.
+    pub fn validate_geometry_extents(&self) -> Vec<usize> {
+        // about 10 cm, well above the f32 rounding of projected coordinates
+        const TOLERANCE_DEG: f64 = 1e-6;
+
+        self.s57
+            .iter()
+            .enumerate()
+            .filter(|(_, s57)| {
+                let Some(extent) = s57.extent() else {
+                    return false;
+                };
+
+                s57.positions().any(|position| {
+                    position.lat > extent.top_left.lat + TOLERANCE_DEG
+                        || position.lat < extent.bottom_right.lat - TOLERANCE_DEG
+                        || position.lon < extent.top_left.lon - TOLERANCE_DEG
+                        || position.lon > extent.bottom_right.lon + TOLERANCE_DEG
+                })
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn total_vertices(&self) -> usize {
+        self.s57.iter().map(|s57| s57.vertex_count()).sum()
+    }
+
+    /// Sounding datum as stored in the header
+    pub fn sounding_datum(&self) -> &str {
+        self.soundingdatum.trim_end_matches('\0')
+    }
+
+    pub fn sounding_datum_typed(&self) -> Option<SoundingDatum> {
+        self.sounding_datum().parse().ok()
+    }
+
+    /// Vertical datum of the cell as declared by the M_VDAT meta-feature
+    pub fn vertical_datum(&self) -> Option<VerticalDatum> {
+        self.s57
+            .iter()
+            .filter(|s57| s57.s57_type() == S57Type::M_VDAT)
+            .find_map(|s57| s57.vertical_datum())
+    }
+
+    /// Horizontal datum of the cell as declared by the M_HDAT meta-feature
+    pub fn horizontal_datum(&self) -> Option<HorizontalDatum> {
+        self.s57
+            .iter()
+            .filter(|s57| s57.s57_type() == S57Type::M_HDAT)
+            .find_map(|s57| s57.horizontal_datum())
+    }
+
+    /// Vertical datum for a feature, falling back to the cell default
+    pub fn feature_vertical_datum(&self, s57: &S57) -> Option<VerticalDatum> {
+        s57.vertical_datum().or_else(|| self.vertical_datum())
+    }
+
+    /// Horizontal datum for a feature, falling back to the cell default
+    pub fn feature_horizontal_datum(&self, s57: &S57) -> Option<HorizontalDatum> {
+        s57.horizontal_datum().or_else(|| self.horizontal_datum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_support::ChartBuilder;
+
+    fn parse(bytes: Vec<u8>) -> ChartFile {
+        ChartFile::parse_file(&mut Cursor::new(bytes)).unwrap()
+    }
+
+    #[test]
+    fn value_type_mismatch_needs_diagnostics() {
+        let bytes = ChartBuilder::new("MISMATCH")
+            .feature(S57Type::UWTROC, 1, 1)
+            .int_attribute(S57Attribute::VALSOU as u16, 4)
+            .point(54.5, 10.5)
+            .build();
+
+        let chart = parse(bytes.clone());
+        assert!(chart.warnings().is_empty());
+        assert_eq!(
+            chart.features()[0].attribute(S57Attribute::VALSOU),
+            Some(&AttributeValue::Double(4.0))
+        );
+
+        let options = ParseOptions {
+            diagnostics: true,
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+        assert_eq!(
+            *chart.warnings(),
+            vec![ParseWarning::ValueTypeMismatch {
+                attribute_code: S57Attribute::VALSOU as u16,
+                value_type: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn record_filter_keeps_only_soundings() {
+        let bytes = ChartBuilder::new("FILTER")
+            .feature(S57Type::SOUNDG, 1, 4)
+            .soundings(&[(0.0, 0.0, 4.5), (10.0, 10.0, 6.0)])
+            .feature(S57Type::COALNE, 2, 2)
+            .int_attribute(S57Attribute::CATCOA as u16, 1)
+            .line(&[[1, 1, 2, 1]])
+            .edges(&[(1, &[(50.0, 0.0)])])
+            .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0)])
+            .build();
+
+        let options = ParseOptions {
+            record_filter: Some(HashSet::from([
+                FEATURE_ID_RECORD,
+                FEATURE_GEOMETRY_RECORD_MULTIPOINT,
+            ])),
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+
+        let [soundings, coastline] = chart.features() else {
+            panic!("expected two features");
+        };
+        assert_eq!(soundings.multi_point_geometry().len(), 2);
+        assert!(coastline.lines().is_empty());
+        assert!(coastline.attribute_list().is_empty());
+        assert_eq!(coastline.feature_id(), 2);
+    }
+
+    #[test]
+    fn truncated_records_are_malformed() {
+        let malformed = |bytes: Vec<u8>| {
+            matches!(
+                ChartFile::parse_file(&mut Cursor::new(bytes)),
+                Err(ChartError::MalformedHeader)
+            )
+        };
+
+        // record length shorter than the record header
+        let mut bytes = ChartBuilder::new("SHORT").build();
+        bytes.extend_from_slice(&64u16.to_le_bytes());
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        assert!(malformed(bytes));
+
+        // feature id record with a missing byte
+        assert!(malformed(
+            ChartBuilder::new("SHORT").record(64, &[1, 0, 1, 0]).build()
+        ));
+
+        // line element cut in half
+        assert!(malformed(
+            ChartBuilder::new("SHORT")
+                .feature(S57Type::COALNE, 1, 2)
+                .record(81, &[0; 36 + 8])
+                .build()
+        ));
+
+        // line record without its extent header
+        assert!(malformed(
+            ChartBuilder::new("SHORT")
+                .feature(S57Type::COALNE, 1, 2)
+                .record(81, &[0; 12])
+                .build()
+        ));
+    }
+
+    #[test]
+    fn grace_period_chart_needs_allow_expired() {
+        let bytes = ChartBuilder::new("GRACE")
+            .server_status([1, 1, 0, 0, 30, 12])
+            .build();
+
+        let result = ChartFile::parse_file(&mut Cursor::new(bytes.clone()));
+        assert!(matches!(result, Err(ChartError::Expired)));
+
+        let options = ParseOptions {
+            allow_expired: true,
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+        assert_eq!(
+            chart.subscription_status(),
+            Some(SubscriptionStatus::InGrace { days_remaining: 12 })
+        );
+        assert!(!chart.status().expired);
+    }
+
+    #[test]
+    fn failed_decryption_is_reported_as_encrypted() {
+        let bytes = ChartBuilder::new("LOCKED")
+            .server_status([1, 0, 1, 30, 0, 0])
+            .build();
+
+        let result = ChartFile::parse_file(&mut Cursor::new(bytes));
+        assert!(matches!(result, Err(ChartError::Encrypted)));
+    }
+
+    #[test]
+    fn colours_of_red_and_green_buoys() {
+        let chart = parse(
+            ChartBuilder::new("COLOURS")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .string_attribute(S57Attribute::COLOUR as u16, "3")
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .string_attribute(S57Attribute::COLOUR as u16, "4")
+                .point(54.5, 10.6)
+                .feature(S57Type::BOYLAT, 3, 1)
+                .string_attribute(S57Attribute::COLOUR as u16, "3")
+                .point(54.5, 10.7)
+                .build(),
+        );
+
+        assert_eq!(
+            chart.colours_used(),
+            BTreeSet::from([S52Colour::Red, S52Colour::Green])
+        );
+    }
+
+    #[test]
+    fn feature_ids_are_kept() {
+        let chart = parse(
+            ChartBuilder::new("IDS")
+                .feature(S57Type::BCNLAT, 7, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::LIGHTS, 9, 1)
+                .point(54.5, 10.5)
+                .build(),
+        );
+
+        let ids: Vec<u16> = chart
+            .features()
+            .iter()
+            .map(|s57| s57.feature_id())
+            .collect();
+        assert_eq!(ids, vec![7, 9]);
+    }
+
+    #[test]
+    fn applied_updates_grow_in_sequence() {
+        let base = parse(ChartBuilder::new("CELL").version(3, 0).build());
+        assert!(base.applied_updates().is_empty());
+
+        let updated = parse(
+            ChartBuilder::new("CELL")
+                .version(3, 1)
+                .record(6, &2u16.to_le_bytes())
+                .build(),
+        );
+        assert_eq!(updated.applied_updates(), [1, 2]);
+        assert_eq!(updated.update(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let chart = parse(
+            ChartBuilder::new("SERDE")
+                .feature(S57Type::DEPCNT, 1, 2)
+                .int_attribute(S57Attribute::VALDCO as u16, 10)
+                .line(&[[1, 1, 2, 1]])
+                .feature(S57Type::LNDMRK, 2, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "Tower")
+                .point(54.5, 10.5)
+                .edges(&[(1, &[(50.0, 0.0)])])
+                .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0)])
+                .build(),
+        );
+
+        let json = serde_json::to_value(&chart).unwrap();
+        assert_eq!(json["s57"][0]["s57_type"], "DEPCNT");
+        assert_eq!(
+            json["s57"][1]["attributes"]["OBJNAM"],
+            serde_json::json!({ "type": "String", "value": "Tower" })
+        );
+
+        let decoded: ChartFile =
+            serde_json::from_str(&serde_json::to_string(&chart).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+        assert_eq!(decoded.features()[0].lines(), chart.features()[0].lines());
+    }
+
+    #[test]
+    fn geometry_is_built_from_trailing_tables() {
+        let chart = parse(
+            ChartBuilder::new("TABLES")
+                .feature(S57Type::COALNE, 1, 2)
+                .line(&[[1, 1, 2, 1]])
+                .feature(S57Type::LNDARE, 2, 3)
+                .area(&[[1, 1, 2, 1], [2, 2, 1, 1]])
+                .edges(&[(1, &[(50.0, 0.0)]), (2, &[(50.0, 100.0)])])
+                .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0)])
+                .build(),
+        );
+
+        let coastline = &chart.features()[0];
+        assert_eq!(coastline.lines().len(), 1);
+        assert_eq!(coastline.lines()[0].len(), 3);
+
+        let land = &chart.features()[1];
+        assert_eq!(land.polygons().len(), 1);
+        assert_eq!(land.polygons()[0].first(), land.polygons()[0].last());
+    }
+
+    #[test]
+    fn streaming_stops_early_with_built_lines() {
+        let mut builder = ChartBuilder::new("STREAM");
+        for id in 1..=3 {
+            builder = builder
+                .feature(S57Type::DEPCNT, id, 2)
+                .line(&[[1, 1, 2, 1]]);
+        }
+        let bytes = builder
+            .edges(&[(1, &[(50.0, 0.0)])])
+            .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0)])
+            .build();
+
+        let mut streamed = Vec::new();
+        stream_features(&mut Cursor::new(bytes), &ParseOptions::default(), |s57| {
+            streamed.push(s57);
+            if streamed.len() == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(streamed.len(), 2);
+        for s57 in &streamed {
+            assert_eq!(s57.lines().len(), 1);
+            assert_eq!(s57.lines()[0].len(), 3);
+        }
+    }
+
+    #[test]
+    fn duplicate_buoy_is_found() {
+        let chart = parse(
+            ChartBuilder::new("DUPES")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .point(54.6, 10.5)
+                .feature(S57Type::BOYLAT, 3, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::LNDMRK, 4, 1)
+                .point(54.5, 10.5)
+                .build(),
+        );
+
+        assert_eq!(chart.find_duplicates(), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn line_endpoints_half_a_metre_apart_are_snapped() {
+        let mut chart = parse(
+            ChartBuilder::new("SNAP")
+                .feature(S57Type::COALNE, 1, 2)
+                .line(&[[1, 1, 2, 1]])
+                .feature(S57Type::COALNE, 2, 2)
+                .line(&[[3, 2, 4, 1]])
+                .edges(&[(1, &[(50.0, 0.0)]), (2, &[(150.0, 0.0)])])
+                .nodes(&[
+                    (1, 0.0, 0.0),
+                    (2, 100.0, 0.0),
+                    (3, 100.5, 0.0),
+                    (4, 200.0, 0.0),
+                ])
+                .build(),
+        );
+
+        let end = |chart: &ChartFile| *chart.features()[0].lines()[0].last().unwrap();
+        let start = |chart: &ChartFile| *chart.features()[1].lines()[0].first().unwrap();
+        assert_ne!(end(&chart), start(&chart));
+
+        chart.snap_topology(1.0);
+
+        assert_eq!(end(&chart), start(&chart));
+        assert_eq!(chart.connected_nodes()[&3].position(), &end(&chart));
+    }
+
+    #[test]
+    fn nodes_a_decimetre_apart_are_merged() {
+        let mut chart = parse(
+            ChartBuilder::new("NODES")
+                .nodes(&[(1, 20.0, 20.0), (2, 20.1, 20.0), (3, 40.0, 20.0)])
+                .build(),
+        );
+
+        chart.merge_coincident_nodes(0.5);
+
+        let nodes = chart.connected_nodes();
+        assert_eq!(nodes[&1].position(), nodes[&2].position());
+        assert_ne!(nodes[&1].position(), nodes[&3].position());
+    }
+
+    #[test]
+    fn merged_nodes_rebuild_lines() {
+        let mut chart = parse(
+            ChartBuilder::new("MERGE")
+                .feature(S57Type::COALNE, 1, 2)
+                .line(&[[1, 1, 3, 1]])
+                .edges(&[(1, &[(50.0, 0.0)])])
+                .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0), (3, 100.5, 0.0)])
+                .build(),
+        );
+
+        chart.merge_coincident_nodes(1.0);
+
+        let anchor = *chart.connected_nodes()[&2].position();
+        let lines = chart.features()[0].lines();
+        assert_eq!(lines[0].last(), Some(&anchor));
+    }
+
+    #[test]
+    fn unhandled_value_type_is_reported() {
+        let mut attribute = (S57Attribute::VALSOU as u16).to_le_bytes().to_vec();
+        attribute.push(1);
+        attribute.extend_from_slice(&2.5f32.to_le_bytes());
+
+        let bytes = ChartBuilder::new("TYPE1")
+            .feature(S57Type::UWTROC, 1, 1)
+            .record(FEATURE_ATTRIBUTE_RECORD, &attribute)
+            .point(54.5, 10.5)
+            .build();
+
+        let options = ParseOptions {
+            diagnostics: true,
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+
+        assert_eq!(
+            *chart.warnings(),
+            vec![ParseWarning::UnhandledValueType {
+                attribute_code: S57Attribute::VALSOU as u16,
+                value_type: 1,
+            }]
+        );
+        assert_eq!(chart.features()[0].attribute(S57Attribute::VALSOU), None);
+    }
+
+    #[test]
+    fn filter_keeps_matching_features_and_header() {
+        let chart = parse(
+            ChartBuilder::new("THEMED")
+                .version(4, 2)
+                .feature(S57Type::BOYLAT, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::LNDMRK, 2, 1)
+                .point(54.6, 10.5)
+                .feature(S57Type::BOYLAT, 3, 1)
+                .point(54.7, 10.5)
+                .build(),
+        );
+
+        let buoys = chart.filter_features(|s57| s57.s57_type() == S57Type::BOYLAT);
+
+        let ids: Vec<u16> = buoys
+            .features()
+            .iter()
+            .map(|s57| s57.feature_id())
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(buoys.name(), "THEMED");
+        assert_eq!(buoys.edition(), 4);
+        assert_eq!(buoys.extent(), chart.extent());
+    }
+
+    #[test]
+    fn version_line_uses_update_date() {
+        let chart = parse(
+            ChartBuilder::new("VERSION")
+                .version(4, 12)
+                .record(HEADER_CELL_PUBLISHDATE, b"20231115\0")
+                .record(HEADER_CELL_UPDATEDATE, b"20240301\0")
+                .build(),
+        );
+
+        assert_eq!(
+            chart.version_info().to_string(),
+            "Ed. 4, Upd. 12 (2024-03-01)"
+        );
+
+        let base = parse(
+            ChartBuilder::new("VERSION")
+                .version(4, 0)
+                .record(HEADER_CELL_PUBLISHDATE, b"20231115\0")
+                .build(),
+        );
+        assert_eq!(
+            base.version_info().to_string(),
+            "Ed. 4, Upd. 0 (2023-11-15)"
+        );
+    }
+
+    #[test]
+    fn server_status_after_features() {
+        let bytes = ChartBuilder::new("ORDER")
+            .feature(S57Type::LNDMRK, 1, 1)
+            .point(54.5, 10.5)
+            .server_status([1, 1, 1, 30, 0, 0])
+            .build();
+
+        let strict = ParseOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let result = ChartFile::parse_file_with_options(&mut Cursor::new(bytes.clone()), &strict);
+        assert!(matches!(
+            result,
+            Err(ChartError::UnexpectedRecord(SERVER_STATUS_RECORD))
+        ));
+
+        let chart = parse(bytes);
+        assert_eq!(
+            *chart.warnings(),
+            vec![ParseWarning::RecordOutOfSection {
+                record_type: SERVER_STATUS_RECORD
+            }]
+        );
+        assert_eq!(chart.features().len(), 1);
+    }
+
+    #[test]
+    fn features_keep_file_order() {
+        let chart = parse(
+            ChartBuilder::new("ORDER")
+                .feature(S57Type::LNDMRK, 5, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 9, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .point(54.5, 10.5)
+                .build(),
+        );
+
+        let ids = |features: Vec<&S57>| -> Vec<u16> {
+            features.iter().map(|s57| s57.feature_id()).collect()
+        };
+        assert_eq!(ids(chart.features().iter().collect()), vec![5, 9, 2]);
+        assert_eq!(ids(chart.sorted_features()), vec![2, 9, 5]);
+    }
+
+    #[test]
+    fn search_by_name_ignores_case_and_accents() {
+        let chart = parse(
+            ChartBuilder::new("NAMES")
+                .feature(S57Type::SLCONS, 1, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "North  Pier")
+                .point(54.5, 10.5)
+                .feature(S57Type::SLCONS, 2, 1)
+                .string_attribute(S57Attribute::NOBJNM as u16, "Südmole")
+                .point(54.5, 10.5)
+                .feature(S57Type::LNDMRK, 3, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "Lighthouse")
+                .point(54.5, 10.5)
+                .build(),
+        );
+
+        let ids = |query: &str| -> Vec<u16> {
+            chart
+                .search_by_name(query)
+                .iter()
+                .map(|s57| s57.feature_id())
+                .collect()
+        };
+        assert_eq!(ids("pier"), vec![1]);
+        assert_eq!(ids("north pier"), vec![1]);
+        assert_eq!(ids("SUDMOLE"), vec![2]);
+        assert!(ids("harbour").is_empty());
+    }
+
+    #[test]
+    fn encrypted_oesenc_is_rejected() {
+        // record type and size of an encrypted payload are random bytes
+        let bytes = vec![0x9a, 0x3f, 10, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef];
+
+        let result = ChartFile::parse_file(&mut Cursor::new(bytes));
+        assert!(matches!(result, Err(ChartError::Encrypted)));
+    }
+
+    #[test]
+    fn rock_that_covers_and_uncovers_is_intertidal() {
+        let chart = parse(
+            ChartBuilder::new("ROCKS")
+                .feature(S57Type::UWTROC, 1, 1)
+                .int_attribute(S57Attribute::WATLEV as u16, 4)
+                .point(54.5, 10.5)
+                .feature(S57Type::UWTROC, 2, 1)
+                .int_attribute(S57Attribute::WATLEV as u16, 2)
+                .point(54.5, 10.6)
+                .feature(S57Type::UWTROC, 3, 1)
+                .point(54.5, 10.7)
+                .build(),
+        );
+
+        let ids: Vec<u16> = chart
+            .intertidal_features()
+            .iter()
+            .map(|s57| s57.feature_id())
+            .collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    #[test]
+    fn layer_stats_per_object_class() {
+        let chart = parse(
+            ChartBuilder::new("LAYERS")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "K 1")
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .point(54.5, 10.6)
+                .feature(S57Type::LNDMRK, 3, 1)
+                .point(54.5, 10.7)
+                .build(),
+        );
+
+        assert_eq!(
+            chart.layer_stats_json(),
+            concat!(
+                r#"{"BOYLAT":{"count":2,"vertex_count":2,"has_names":true},"#,
+                r#""LNDMRK":{"count":1,"vertex_count":1,"has_names":false}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn point_layer_includes_each_sounding() {
+        let chart = parse(
+            ChartBuilder::new("POINTS")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::SOUNDG, 2, 4)
+                .soundings(&[(0.0, 0.0, 5.0), (100.0, 100.0, 7.5)])
+                .build(),
+        );
+
+        let ids = |layer: Vec<(&S57, Position)>| -> Vec<u16> {
+            layer.iter().map(|(s57, _)| s57.feature_id()).collect()
+        };
+        assert_eq!(ids(chart.point_layer()), vec![1]);
+        assert_eq!(ids(chart.point_layer_with_soundings()), vec![1, 2, 2]);
+        assert_eq!(
+            chart.point_layer()[0].1,
+            Position {
+                lat: 54.5,
+                lon: 10.5
+            }
+        );
+    }
+
+    #[test]
+    fn feature_offsets_point_at_their_id_records() {
+        let first = ChartBuilder::new("OFFSETS").build().len() as u64;
+        let second = ChartBuilder::new("OFFSETS")
+            .feature(S57Type::LNDMRK, 1, 1)
+            .point(54.5, 10.5)
+            .build()
+            .len() as u64;
+        let bytes = ChartBuilder::new("OFFSETS")
+            .feature(S57Type::LNDMRK, 1, 1)
+            .point(54.5, 10.5)
+            .feature(S57Type::LNDMRK, 2, 1)
+            .point(54.5, 10.6)
+            .build();
+
+        let options = ParseOptions {
+            record_offsets: true,
+            ..Default::default()
+        };
+        let chart =
+            ChartFile::parse_file_with_options(&mut Cursor::new(bytes.clone()), &options).unwrap();
+        let offsets: Vec<_> = chart.features().iter().map(S57::file_offset).collect();
+        assert_eq!(offsets, vec![Some(first), Some(second)]);
+
+        // FEATURE_ID record type
+        assert_eq!(
+            bytes[second as usize..second as usize + 2],
+            64u16.to_le_bytes()
+        );
+
+        let chart = parse(bytes);
+        assert_eq!(chart.features()[0].file_offset(), None);
+    }
+
+    #[test]
+    fn feature_density_grid() {
+        let chart = parse(
+            ChartBuilder::new("DENSITY")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.8, 10.2)
+                .feature(S57Type::LNDMRK, 2, 1)
+                .point(54.2, 10.8)
+                .feature(S57Type::LNDMRK, 3, 1)
+                .point(54.3, 10.9)
+                // outside the extent, counted in the north-west cell
+                .feature(S57Type::LNDMRK, 4, 1)
+                .point(56.0, 9.0)
+                .build(),
+        );
+
+        assert_eq!(chart.feature_density(2, 2), vec![vec![2, 0], vec![0, 2]]);
+        assert_eq!(chart.feature_density(3, 1), vec![vec![2, 0, 2]]);
+        assert!(chart.feature_density(0, 2).iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn parse_timing_is_only_measured_on_request() {
+        let bytes = ChartBuilder::new("TIMING").build();
+
+        let chart = parse(bytes.clone());
+        assert_eq!(chart.parsed_at(), None);
+        assert_eq!(chart.parse_duration(), None);
+
+        let before = SystemTime::now();
+        let options = ParseOptions {
+            measure_timing: true,
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+
+        assert!(chart.parsed_at().is_some_and(|at| at >= before));
+        assert!(chart
+            .parse_duration()
+            .is_some_and(|duration| duration <= before.elapsed().unwrap()));
+    }
+
+    #[test]
+    fn parse_report_counts_skipped_data() {
+        let chart = parse(
+            ChartBuilder::new("REPORT")
+                // attribute before the first feature id record
+                .int_attribute(S57Attribute::CONVIS as u16, 1)
+                .record(98, &[0; 8])
+                .record(99, &[0; 8])
+                .record(99, &[0; 8])
+                .feature(S57Type::LNDMRK, 1, 1)
+                .int_attribute(9999, 1)
+                .point(54.5, 10.5)
+                .build(),
+        );
+
+        assert_eq!(
+            *chart.report(),
+            ParseReport {
+                skipped_records: BTreeMap::from([(98, 1), (99, 2)]),
+                orphaned_records: 1,
+                unknown_attributes: 1,
+                warnings: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn attributes_shared_by_all_buoys() {
+        let chart = parse(
+            ChartBuilder::new("COMMON")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .int_attribute(S57Attribute::CATLAM as u16, 1)
+                .string_attribute(S57Attribute::COLOUR as u16, "3")
+                .string_attribute(S57Attribute::OBJNAM as u16, "K 1")
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .int_attribute(S57Attribute::CATLAM as u16, 2)
+                .string_attribute(S57Attribute::COLOUR as u16, "4")
+                .point(54.5, 10.6)
+                .build(),
+        );
+        let features: Vec<&S57> = chart.features().iter().collect();
+
+        assert_eq!(
+            chart.common_attributes(&features),
+            BTreeSet::from([S57Attribute::CATLAM, S57Attribute::COLOUR])
+        );
+        assert_eq!(chart.common_attributes(&features[..1]).len(), 3);
+        assert!(chart.common_attributes(&[]).is_empty());
+    }
+
+    #[test]
+    fn sounding_datum_by_name_or_abbreviation() {
+        let datum = |name: &str| {
+            parse(
+                ChartBuilder::new("DATUM")
+                    .record(9, format!("{}\0", name).as_bytes())
+                    .build(),
+            )
+            .sounding_datum_typed()
+        };
+
+        assert_eq!(
+            datum("Lowest  Astronomical Tide"),
+            Some(VerticalDatum::LowestAstronomicalTide)
+        );
+        assert_eq!(datum("MLLW"), Some(VerticalDatum::MeanLowerLowWater));
+        assert_eq!(datum("chart datum"), None);
+    }
+
+    #[test]
+    fn draw_layers_by_priority() {
+        let chart = parse(
+            ChartBuilder::new("LAYERS")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::M_COVR, 2, 3)
+                .feature(S57Type::DEPARE, 3, 3)
+                .feature(S57Type::LNDMRK, 4, 1)
+                .point(54.5, 10.6)
+                .build(),
+        );
+
+        let layers: Vec<(u8, Vec<u16>)> = chart
+            .features_by_priority()
+            .into_iter()
+            .map(|(priority, features)| {
+                (
+                    priority,
+                    features.iter().map(|s57| s57.feature_id()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(layers, vec![(1, vec![3]), (7, vec![1, 4])]);
+    }
+
+    #[test]
+    fn features_missing_their_geometry() {
+        let chart = parse(
+            ChartBuilder::new("PRIMITIVES")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::LNDMRK, 2, 1)
+                .feature(S57Type::NAVLNE, 3, 2)
+                .point(54.5, 10.6)
+                // meta object without a primitive
+                .feature(S57Type::M_COVR, 4, 255)
+                .build(),
+        );
+
+        assert_eq!(
+            chart.validate_primitives(),
+            vec![
+                (1, PrimitiveMismatch::Missing(GeometryPrimitive::Point)),
+                (2, PrimitiveMismatch::Missing(GeometryPrimitive::Line)),
+                (
+                    2,
+                    PrimitiveMismatch::Unexpected {
+                        declared: GeometryPrimitive::Line,
+                        found: GeometryPrimitive::Point,
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn soundings_are_unprojected_about_the_reference() {
+        let bytes = ChartBuilder::new("REFERENCE")
+            .feature(S57Type::SOUNDG, 1, 1)
+            .soundings(&[(0.0, 0.0, 5.0)])
+            .build();
+        let sounding = |chart: &ChartFile| chart.features()[0].multi_point_geometry()[0].position;
+        let close = |a: Position, lat: f64, lon: f64| {
+            (a.lat - lat).abs() < 1e-9 && (a.lon - lon).abs() < 1e-9
+        };
+
+        let chart = parse(bytes.clone());
+        assert!(close(sounding(&chart), 54.5, 10.5));
+
+        let options = ParseOptions {
+            projection_reference: Some(Position {
+                lat: 54.0,
+                lon: 10.0,
+            }),
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+        assert!(close(sounding(&chart), 54.0, 10.0));
+    }
+
+    #[test]
+    fn features_referencing_a_text_file() {
+        let chart = parse(
+            ChartBuilder::new("TXTDSC")
+                .feature(S57Type::RESARE, 1, 3)
+                .string_attribute(S57Attribute::TXTDSC as u16, "DE1RES01.TXT")
+                .feature(S57Type::RESARE, 2, 3)
+                .string_attribute(S57Attribute::NTXTDS as u16, "de1res01.txt")
+                .feature(S57Type::RESARE, 3, 3)
+                .string_attribute(S57Attribute::TXTDSC as u16, "DE1RES02.TXT")
+                .build(),
+        );
+
+        let ids: Vec<u16> = chart
+            .features_referencing_text(" DE1RES01.TXT ")
+            .iter()
+            .map(|s57| s57.feature_id())
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(chart.features_referencing_text("").is_empty());
+    }
+
+    #[test]
+    fn summary_of_a_small_chart() {
+        let chart = parse(
+            ChartBuilder::new("DE521010\0")
+                .version(3, 2)
+                .record(7, &22000u32.to_le_bytes())
+                .feature(S57Type::BOYLAT, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .point(54.5, 10.6)
+                .feature(S57Type::LNDMRK, 3, 1)
+                .point(54.5, 10.7)
+                .build(),
+        );
+
+        assert_eq!(
+            chart.summary(),
+            ChartSummary {
+                name: "DE521010".to_string(),
+                scale: 22000,
+                edition: 3,
+                update: 2,
+                extent_corners: [[10.0, 55.0], [11.0, 55.0], [11.0, 54.0], [10.0, 54.0]],
+                feature_counts: BTreeMap::from([
+                    ("BOYLAT".to_string(), 2),
+                    ("LNDMRK".to_string(), 1)
+                ]),
+                subscription_status: None,
+            }
+        );
+    }
+
+    #[test]
+    fn features_without_attributes() {
+        let chart = parse(
+            ChartBuilder::new("BARE")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "Kirche")
+                .point(54.5, 10.5)
+                .feature(S57Type::LNDMRK, 2, 1)
+                .point(54.5, 10.6)
+                .build(),
+        );
+
+        let ids: Vec<u16> = chart
+            .attributeless_features()
+            .iter()
+            .map(|s57| s57.feature_id())
+            .collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn pixel_dimensions_of_a_one_degree_cell() {
+        let chart = parse(ChartBuilder::new("PIXELS").build());
+
+        // 1° of longitude is about 65 km at 54.5°N, 1° of latitude about
+        // 111 km once stretched by mercator and scaled back to true length
+        assert_eq!(chart.pixel_dimensions(50_000, 96.0), (4886, 8415));
+        assert_eq!(chart.pixel_dimensions(100_000, 96.0), (2443, 4208));
+    }
+
+    #[test]
+    fn thinning_keeps_the_shoaler_sounding() {
+        let chart = parse(
+            ChartBuilder::new("THIN")
+                .feature(S57Type::SOUNDG, 1, 1)
+                .soundings(&[(0.0, 0.0, 5.0), (10.0, 0.0, 3.0), (500.0, 0.0, 8.0)])
+                .build(),
+        );
+
+        let depths: Vec<f64> = chart
+            .thinned_soundings(100.0)
+            .iter()
+            .map(|(_, depth)| *depth)
+            .collect();
+        assert_eq!(depths, vec![3.0, 8.0]);
+        assert_eq!(chart.thinned_soundings(1.0).len(), 3);
+    }
+
+    #[test]
+    fn features_on_the_chart_boundary() {
+        let chart = parse(
+            ChartBuilder::new("EDGES")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.5, 10.0)
+                .feature(S57Type::LNDMRK, 2, 1)
+                .point(54.5, 10.5)
+                // about 11 m north of the south edge
+                .feature(S57Type::LNDMRK, 3, 1)
+                .point(54.0001, 10.5)
+                .feature(S57Type::LNDMRK, 4, 1)
+                .point(56.0, 10.5)
+                .build(),
+        );
+
+        let ids = |tolerance_m: f64| -> Vec<u16> {
+            chart
+                .boundary_features(tolerance_m)
+                .iter()
+                .map(|s57| s57.feature_id())
+                .collect()
+        };
+        assert_eq!(ids(50.0), vec![1, 3, 4]);
+        assert_eq!(ids(5.0), vec![1, 4]);
+    }
+
+    #[test]
+    fn distinct_depth_contours_shallowest_first() {
+        let mut builder = ChartBuilder::new("CONTOURS");
+        for (id, depth) in [(1, "10"), (2, "2.5"), (3, "10"), (4, "5")] {
+            builder = builder
+                .feature(S57Type::DEPCNT, id, 2)
+                .string_attribute(S57Attribute::VALDCO as u16, depth);
+        }
+        let chart = parse(
+            builder
+                .feature(S57Type::DEPARE, 5, 3)
+                .int_attribute(S57Attribute::VALDCO as u16, 20)
+                .build(),
+        );
+
+        assert_eq!(chart.depth_contours(), vec![2.5, 5.0, 10.0]);
+    }
+
+    #[test]
+    fn multipoint_soundings_keep_their_depths() {
+        let chart = parse(
+            ChartBuilder::new("MULTIPOINT")
+                .feature(S57Type::SOUNDG, 1, 4)
+                .soundings(&[(0.0, 0.0, 4.2), (100.0, -50.0, 12.5)])
+                // declared as a point by some encoders
+                .feature(S57Type::SOUNDG, 2, 1)
+                .soundings(&[(10.0, 10.0, 7.0)])
+                .build(),
+        );
+
+        let soundings = &chart.features()[0];
+        assert_eq!(soundings.primitive(), GeometryPrimitive::Multipoint);
+        let depths: Vec<f64> = soundings
+            .multi_point_geometry()
+            .iter()
+            .map(|point| point.value)
+            .collect();
+        assert_eq!(depths, vec![4.2f32 as f64, 12.5]);
+        assert!(chart.validate_primitives().is_empty());
+    }
+
+    #[test]
+    fn skewed_extent_keeps_its_corners() {
+        let corners: Vec<u8> = [54.0, 10.0, 55.0, 10.2, 55.0, 11.2, 54.0, 11.0f64]
+            .into_iter()
+            .flat_map(f64::to_le_bytes)
+            .collect();
+        let chart = parse(ChartBuilder::new("SKEWED").record(100, &corners).build());
+
+        let position = |lat, lon| Position { lat, lon };
+        assert_eq!(
+            chart.extent_polygon(),
+            vec![
+                position(54.0, 10.0),
+                position(55.0, 10.2),
+                position(55.0, 11.2),
+                position(54.0, 11.0),
+                position(54.0, 10.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn header_metadata_accessors() {
+        let chart = parse(
+            ChartBuilder::new("OC-49-M11SO4\0")
+                .record(3, b"20230115\0")
+                .record(5, b"20240301\0")
+                .version(4, 7)
+                .record(7, &12000u32.to_le_bytes())
+                .build(),
+        );
+
+        assert_eq!(chart.name(), "OC-49-M11SO4");
+        assert_eq!(chart.publish_date(), "20230115");
+        assert_eq!(chart.update_date(), "20240301");
+        assert_eq!(chart.edition(), 4);
+        assert_eq!(chart.update(), 7);
+        assert_eq!(chart.native_scale(), 12000);
+    }
+
+    #[test]
+    fn edge_table_is_unprojected_by_index() {
+        let chart = parse(
+            ChartBuilder::new("EDGES")
+                .edges(&[(1, &[(0.0, 0.0), (1000.0, 0.0)]), (7, &[])])
+                .build(),
+        );
+
+        assert_eq!(chart.vector_edges.len(), 2);
+        assert!(chart.vector_edges[&7].positions().is_empty());
+
+        let edge = chart.vector_edges[&1].positions();
+        assert_eq!(edge.len(), 2);
+        assert!((edge[0].lat - 54.5).abs() < 1e-9 && (edge[0].lon - 10.5).abs() < 1e-9);
+        // 1000 projected metres east on the simple mercator
+        let lon = 10.5 + 1000.0 / (s57::DEGREE * s57::WGS84_SEMIMAJOR_AXIS_METERS * 0.9996);
+        assert!((edge[1].lat - 54.5).abs() < 1e-9 && (edge[1].lon - lon).abs() < 1e-9);
+    }
+
+    #[test]
+    fn connected_nodes_by_index() {
+        let chart = parse(
+            ChartBuilder::new("NODES")
+                .nodes(&[(3, 0.0, 0.0), (9, 0.0, 1000.0)])
+                .build(),
+        );
+
+        let nodes = chart.connected_nodes();
+        assert_eq!(nodes.len(), 2);
+
+        let center = nodes[&3].position();
+        assert!((center.lat - 54.5).abs() < 1e-9 && (center.lon - 10.5).abs() < 1e-9);
+
+        let north = nodes[&9].position();
+        assert!(north.lat > 54.5 && (north.lon - 10.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn attribute_values_against_the_catalogue() {
+        let chart = parse(
+            ChartBuilder::new("CATALOGUE")
+                .feature(S57Type::BOYLAT, 1, 1)
+                .int_attribute(S57Attribute::CATLAM as u16, 1)
+                .string_attribute(S57Attribute::COLOUR as u16, "red")
+                .string_attribute(S57Attribute::OBJNAM as u16, "K 1")
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .string_attribute(S57Attribute::CATLAM as u16, "port")
+                .string_attribute(S57Attribute::COLOUR as u16, "3,1")
+                .point(54.5, 10.6)
+                .build(),
+        );
+
+        let errors: Vec<(usize, S57Attribute, AttributeKind)> = chart
+            .validate_attributes()
+            .into_iter()
+            .map(|error| (error.feature_index, error.attribute, error.expected))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (0, S57Attribute::COLOUR, AttributeKind::List),
+                (1, S57Attribute::CATLAM, AttributeKind::Enumerated),
+            ]
+        );
+    }
+
+    #[test]
+    fn status_of_a_valid_subscription() {
+        let chart = parse(
+            ChartBuilder::new("VALID")
+                .server_status([1, 1, 1, 30, 14, 0])
+                .build(),
+        );
+        assert_eq!(
+            *chart.status(),
+            ChartStatus {
+                expired: false,
+                expire_days_remaining: 30,
+                grace_days_allowed: 14,
+                grace_days_remaining: 0,
+            }
+        );
+        assert_eq!(chart.subscription_status(), Some(SubscriptionStatus::Valid));
+
+        let chart = parse(ChartBuilder::new("NO STATUS").build());
+        assert_eq!(*chart.status(), ChartStatus::default());
+        assert_eq!(chart.subscription_status(), None);
+    }
+
+    #[test]
+    fn expired_chart_is_only_parsed_when_allowed() {
+        let bytes = ChartBuilder::new("EXPIRED")
+            .server_status([1, 1, 0, 0, 0, 0])
+            .feature(S57Type::LNDMRK, 1, 1)
+            .point(54.5, 10.5)
+            .build();
+
+        let result = ChartFile::parse_file(&mut Cursor::new(bytes.clone()));
+        assert!(matches!(result, Err(ChartError::Expired)));
+
+        let options = ParseOptions {
+            allow_expired: true,
+            ..Default::default()
+        };
+        let chart = ChartFile::parse_file_with_options(&mut Cursor::new(bytes), &options).unwrap();
+        assert!(chart.status().expired);
+        assert_eq!(
+            chart.subscription_status(),
+            Some(SubscriptionStatus::Expired)
+        );
+        assert_eq!(chart.features().len(), 1);
+    }
+
+    #[test]
+    fn center_of_mass_is_weighted_by_vertices() {
+        let chart = parse(
+            ChartBuilder::new("MASS")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.0, 10.0)
+                // three soundings at the cell center
+                .feature(S57Type::SOUNDG, 2, 4)
+                .soundings(&[(0.0, 0.0, 5.0), (0.0, 0.0, 6.0), (0.0, 0.0, 7.0)])
+                .build(),
+        );
+
+        let center = chart.feature_center_of_mass().unwrap();
+        assert!((center.lat - 54.375).abs() < 1e-9);
+        assert!((center.lon - 10.375).abs() < 1e-9);
+
+        assert_eq!(
+            parse(ChartBuilder::new("EMPTY").build()).feature_center_of_mass(),
+            None
+        );
+    }
+
+    #[test]
+    fn geometry_outside_its_declared_extent() {
+        let chart = parse(
+            ChartBuilder::new("MISMATCH")
+                .feature(S57Type::DEPCNT, 1, 2)
+                .line(&[[1, 1, 2, 1]])
+                // the end node lies about 60 km north of the cell
+                .feature(S57Type::DEPCNT, 2, 2)
+                .line(&[[1, 1, 3, 1]])
+                // points carry no extent of their own
+                .feature(S57Type::LNDMRK, 3, 1)
+                .point(56.0, 10.5)
+                .edges(&[(1, &[(50.0, 0.0)])])
+                .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0), (3, 0.0, 200_000.0)])
+                .build(),
+        );
+
+        assert!(chart.features()[0].extent().is_some());
+        assert_eq!(chart.validate_geometry_extents(), vec![1]);
+    }
+}
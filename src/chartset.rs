@@ -0,0 +1,124 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use crate::{chartfile::ChartFile, s57::Position};
+
+/// Several cells used together, e.g. all charts of a region
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+pub struct ChartSet {
+    charts: Vec<ChartFile>,
+}
+
+#[allow(dead_code)]
+impl ChartSet {
+    pub fn new(charts: Vec<ChartFile>) -> Self {
+        Self { charts }
+    }
+
+    pub fn push(&mut self, chart: ChartFile) {
+        self.charts.push(chart);
+    }
+
+    pub fn charts(&self) -> &[ChartFile] {
+        &self.charts
+    }
+
+    /// Smallest and largest native scale denominator of the charts,
+    /// `(0, 0)` for an empty set
+    pub fn scale_range(&self) -> (u32, u32) {
+        let scales = self.charts.iter().map(|chart| chart.native_scale());
+
+        match (scales.clone().min(), scales.max()) {
+            (Some(min), Some(max)) => (min, max),
+            _ => (0, 0),
+        }
+    }
+
+    /// Most detailed chart, the one with the smallest scale denominator,
+    /// whose extent contains `position`. Charts without a native scale are
+    /// only picked if nothing else covers the position.
+    pub fn best_chart_at(&self, position: &Position) -> Option<&ChartFile> {
+        self.charts
+            .iter()
+            .filter(|chart| chart.extent().contains(position))
+            .min_by_key(|chart| match chart.native_scale() {
+                0 => u32::MAX,
+                scale => scale,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_support::ChartBuilder;
+
+    fn chart(builder: ChartBuilder, scale: u32) -> ChartFile {
+        let bytes = builder.record(7, &scale.to_le_bytes()).build();
+        ChartFile::parse_file(&mut Cursor::new(bytes)).unwrap()
+    }
+
+    #[test]
+    fn scale_range_of_harbour_and_coastal_charts() {
+        assert_eq!(ChartSet::default().scale_range(), (0, 0));
+
+        let mut set = ChartSet::new(vec![
+            chart(ChartBuilder::new("COASTAL"), 90_000),
+            chart(ChartBuilder::new("HARBOUR"), 8_000),
+        ]);
+        set.push(chart(ChartBuilder::new("APPROACH"), 22_000));
+
+        assert_eq!(set.scale_range(), (8_000, 90_000));
+    }
+
+    #[test]
+    fn most_detailed_chart_at_a_position() {
+        // sw, nw, ne, se of a small harbour cell
+        let harbour_extent: Vec<u8> = [54.3, 10.1, 54.4, 10.1, 54.4, 10.2, 54.3, 10.2f64]
+            .into_iter()
+            .flat_map(f64::to_le_bytes)
+            .collect();
+        let set = ChartSet::new(vec![
+            chart(ChartBuilder::new("UNKNOWN"), 0),
+            chart(ChartBuilder::new("COASTAL"), 90_000),
+            chart(
+                ChartBuilder::new("HARBOUR").record(100, &harbour_extent),
+                8_000,
+            ),
+        ]);
+        let best = |lat, lon| {
+            set.best_chart_at(&Position { lat, lon })
+                .map(|chart| chart.name().to_string())
+        };
+
+        assert_eq!(best(54.35, 10.15), Some("HARBOUR".to_string()));
+        assert_eq!(best(54.8, 10.8), Some("COASTAL".to_string()));
+        assert_eq!(best(56.0, 10.5), None);
+
+        let unknown_only = ChartSet::new(vec![chart(ChartBuilder::new("UNKNOWN"), 0)]);
+        assert!(unknown_only
+            .best_chart_at(&Position {
+                lat: 54.5,
+                lon: 10.5
+            })
+            .is_some());
+    }
+}
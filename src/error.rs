@@ -0,0 +1,71 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use std::{fmt, io};
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum ChartError {
+    /// The payload is still encrypted, e.g. an `.oesenc` file, or the
+    /// server status record reports that decryption failed
+    Encrypted,
+    /// The subscription has expired and the grace period is over
+    Expired,
+    /// SENC version older than the supported 201
+    UnsupportedVersion(u16),
+    /// A header record with an invalid length
+    MalformedHeader,
+    /// Record of this type where it isn't allowed, only in strict mode
+    UnexpectedRecord(u16),
+    Io(io::Error),
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartError::Encrypted => write!(
+                f,
+                "Chart payload is encrypted, expected a decrypted SENC stream"
+            ),
+            ChartError::Expired => write!(f, "Chart expired"),
+            ChartError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported SENC version {}", version)
+            }
+            ChartError::MalformedHeader => write!(f, "Failed to parse header"),
+            ChartError::UnexpectedRecord(record_type) => {
+                write!(f, "Unexpected record of type {}", record_type)
+            }
+            ChartError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ChartError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChartError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ChartError {
+    fn from(err: io::Error) -> Self {
+        ChartError::Io(err)
+    }
+}
@@ -0,0 +1,304 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use std::io::{self, Write};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    chartfile::ChartFile,
+    geometry::{group_rings, MultiGeometryExt},
+    s57::{AttributeValue, MultiGeometry, Position, S57},
+};
+
+fn coordinate(position: &Position) -> Value {
+    json!([position.lon, position.lat])
+}
+
+fn coordinates(geometry: &MultiGeometry) -> Value {
+    Value::Array(geometry.iter().map(coordinate).collect())
+}
+
+fn attribute_value(value: &AttributeValue) -> Value {
+    match value {
+        AttributeValue::UInt32(value) => json!(value),
+        AttributeValue::Double(value) => json!(value),
+        AttributeValue::String(value) => json!(value.trim_end_matches('\0')),
+    }
+}
+
+/// Rings of one polygon, exterior counter-clockwise and holes clockwise
+fn polygon_coordinates(rings: &[&MultiGeometry]) -> Value {
+    Value::Array(
+        rings
+            .iter()
+            .enumerate()
+            .map(|(i, ring)| coordinates(&ring.ensure_winding(i > 0)))
+            .collect(),
+    )
+}
+
+/// RFC 7946 geometry
+fn geometry(s57: &S57) -> Value {
+    if !s57.polygons().is_empty() {
+        let polygons = group_rings(s57.polygons());
+
+        if let [polygon] = polygons.as_slice() {
+            return json!({ "type": "Polygon", "coordinates": polygon_coordinates(polygon) });
+        }
+
+        let polygons: Vec<Value> = polygons
+            .iter()
+            .map(|polygon| polygon_coordinates(polygon))
+            .collect();
+
+        return json!({ "type": "MultiPolygon", "coordinates": polygons });
+    }
+
+    match s57.lines().len() {
+        0 => {}
+        1 => {
+            return json!({ "type": "LineString", "coordinates": coordinates(&s57.lines()[0]) });
+        }
+        _ => {
+            let lines: Vec<Value> = s57.lines().iter().map(coordinates).collect();
+            return json!({ "type": "MultiLineString", "coordinates": lines });
+        }
+    }
+
+    if !s57.multi_point_geometry().is_empty() {
+        let points: Vec<Value> = s57
+            .multi_point_geometry()
+            .iter()
+            .map(|point| coordinate(&point.position))
+            .collect();
+
+        return json!({ "type": "MultiPoint", "coordinates": points });
+    }
+
+    match s57.point_geometry() {
+        Some(position) => json!({ "type": "Point", "coordinates": coordinate(position) }),
+        None => Value::Null,
+    }
+}
+
+#[allow(dead_code)]
+impl S57 {
+    /// Feature as a GeoJSON Feature, attributes keyed by their acronym
+    pub fn to_geojson_feature(&self) -> Value {
+        let mut properties = Map::new();
+
+        properties.insert("type".to_string(), json!(self.s57_type().acronym()));
+        properties.insert("feature_id".to_string(), json!(self.feature_id()));
+
+        // depths of soundings, in the order of the MultiPoint coordinates
+        if !self.multi_point_geometry().is_empty() {
+            let depths: Vec<f64> = self
+                .multi_point_geometry()
+                .iter()
+                .map(|point| point.value)
+                .collect();
+            properties.insert("depths".to_string(), json!(depths));
+        }
+
+        for attribute in self.attribute_list() {
+            if let Some(value) = self.attribute(attribute) {
+                properties.insert(attribute.acronym().to_string(), attribute_value(value));
+            }
+        }
+
+        json!({
+            "type": "Feature",
+            "geometry": geometry(self),
+            "properties": properties,
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl ChartFile {
+    /// Writes all features as a GeoJSON FeatureCollection, one feature at a
+    /// time, without building the whole document in memory
+    pub fn write_geojson<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+
+        w.write_all(b"{\"type\":\"FeatureCollection\",\"features\":[")?;
+
+        for (i, s57) in self.features().iter().enumerate() {
+            if i > 0 {
+                w.write_all(b",")?;
+            }
+            serde_json::to_writer(&mut w, &s57.to_geojson_feature())?;
+        }
+
+        w.write_all(b"]}")?;
+        w.flush()
+    }
+
+    /// All features as a GeoJSON FeatureCollection string
+    pub fn to_geojson(&self) -> String {
+        let mut buf = Vec::new();
+
+        // writing to a Vec can't fail and serde_json only emits UTF-8
+        self.write_geojson(&mut buf)
+            .expect("writing GeoJSON to memory failed");
+        String::from_utf8(buf).expect("GeoJSON is valid UTF-8")
+    }
+
+    /// Writes one GeoJSON Feature per line (GeoJSONL / NDJSON)
+    pub fn write_geojsonl<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+
+        for s57 in self.features() {
+            serde_json::to_writer(&mut w, &s57.to_geojson_feature())?;
+            w.write_all(b"\n")?;
+        }
+
+        w.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{
+        s57::{S57Attribute, S57Type},
+        test_support::ChartBuilder,
+    };
+
+    fn parse(builder: ChartBuilder) -> ChartFile {
+        ChartFile::parse_file(&mut Cursor::new(builder.build())).unwrap()
+    }
+
+    #[test]
+    fn named_landmark_as_point_feature() {
+        let chart = parse(
+            ChartBuilder::new("GEOJSON")
+                .feature(S57Type::LNDMRK, 7, 1)
+                .string_attribute(S57Attribute::OBJNAM as u16, "Leuchtturm")
+                .int_attribute(S57Attribute::CONVIS as u16, 1)
+                .point(54.5, 10.5),
+        );
+
+        assert_eq!(
+            chart.features()[0].to_geojson_feature(),
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [10.5, 54.5] },
+                "properties": {
+                    "type": "LNDMRK",
+                    "feature_id": 7,
+                    "OBJNAM": "Leuchtturm",
+                    "CONVIS": 1,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn streamed_feature_collection() {
+        let empty = parse(ChartBuilder::new("EMPTY"));
+        assert_eq!(
+            empty.to_geojson(),
+            r#"{"type":"FeatureCollection","features":[]}"#
+        );
+
+        let chart = parse(
+            ChartBuilder::new("STREAM")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::BOYLAT, 2, 1)
+                .point(54.6, 10.6),
+        );
+        let mut buf = Vec::new();
+        chart.write_geojson(&mut buf).unwrap();
+
+        let collection: Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(collection["type"], "FeatureCollection");
+        assert_eq!(
+            collection["features"],
+            json!([
+                chart.features()[0].to_geojson_feature(),
+                chart.features()[1].to_geojson_feature(),
+            ])
+        );
+        assert_eq!(String::from_utf8(buf).unwrap(), chart.to_geojson());
+    }
+
+    #[test]
+    fn one_feature_per_line() {
+        let chart = parse(
+            ChartBuilder::new("LINES")
+                .feature(S57Type::LNDMRK, 1, 1)
+                .point(54.5, 10.5)
+                .feature(S57Type::SOUNDG, 2, 4)
+                .soundings(&[(0.0, 0.0, 5.0)]),
+        );
+        let mut buf = Vec::new();
+        chart.write_geojsonl(&mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+
+        assert!(text.ends_with('\n'));
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], chart.features()[0].to_geojson_feature());
+        assert_eq!(lines[1]["geometry"]["type"], "MultiPoint");
+        assert_eq!(lines[1]["properties"]["depths"], json!([5.0]));
+    }
+
+    #[test]
+    fn lines_and_areas_in_the_feature_collection() {
+        let chart = parse(
+            ChartBuilder::new("SHAPES")
+                .feature(S57Type::COALNE, 1, 2)
+                .line(&[[1, 1, 2, 1]])
+                .feature(S57Type::LNDARE, 2, 3)
+                .area(&[[1, 1, 2, 1], [2, 2, 1, 1]])
+                .edges(&[(1, &[(50.0, 0.0)]), (2, &[(50.0, 100.0)])])
+                .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0)]),
+        );
+
+        let collection: Value = serde_json::from_str(&chart.to_geojson()).unwrap();
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+
+        let coastline = &features[0]["geometry"];
+        assert_eq!(coastline["type"], "LineString");
+        assert_eq!(coastline["coordinates"].as_array().unwrap().len(), 3);
+
+        let land = &features[1]["geometry"];
+        assert_eq!(land["type"], "Polygon");
+        let exterior: MultiGeometry = land["coordinates"][0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|coordinate| Position {
+                lon: coordinate[0].as_f64().unwrap(),
+                lat: coordinate[1].as_f64().unwrap(),
+            })
+            .collect();
+        assert_eq!(exterior.first(), exterior.last());
+        // RFC 7946: exterior rings are counter-clockwise
+        assert!(exterior.signed_area() > 0.0);
+    }
+}
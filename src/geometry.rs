@@ -0,0 +1,842 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use crate::s57::{MultiGeometry, Position, Rect, DEGREE, WGS84_SEMIMAJOR_AXIS_METERS};
+
+const OUTCODE_LEFT: u8 = 1;
+const OUTCODE_RIGHT: u8 = 2;
+const OUTCODE_BOTTOM: u8 = 4;
+const OUTCODE_TOP: u8 = 8;
+
+#[allow(dead_code)]
+pub trait MultiGeometryExt {
+    /// Clips a line to `bounds` (Cohen-Sutherland). A line leaving and
+    /// re-entering the bounds is split into several pieces.
+    fn clip_to(&self, bounds: &Rect) -> Vec<MultiGeometry>;
+
+    /// Clips a polygon ring to `bounds` (Sutherland-Hodgman).
+    /// Returns `None` if nothing of the ring is inside.
+    fn clip_polygon_to(&self, bounds: &Rect) -> Option<MultiGeometry>;
+
+    /// Positions as a flat `[lon, lat, lon, lat, ...]` array, longitude first
+    fn to_flat_coords(&self) -> Vec<f64>;
+
+    /// Inserts great-circle points so no segment is longer than `max_segment_m` metres.
+    /// Returns the geometry unchanged unless `max_segment_m` is positive.
+    fn densify(&self, max_segment_m: f64) -> MultiGeometry;
+
+    /// Shoelace area in square degrees (lon/lat), positive for counter-clockwise rings
+    fn signed_area(&self) -> f64;
+
+    /// Area of a ring in square metres on a spherical earth
+    fn area(&self) -> f64;
+
+    /// Ring with the requested winding order, reversed if necessary
+    fn ensure_winding(&self, clockwise: bool) -> MultiGeometry;
+
+    /// Consecutive position pairs
+    fn segments(&self) -> impl Iterator<Item = (&Position, &Position)>;
+
+    /// Whether `position` lies inside the ring (even-odd rule)
+    fn contains(&self, position: &Position) -> bool;
+}
+
+/// Polygon split into its exterior ring and holes
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub exterior: MultiGeometry,
+    pub interiors: Vec<MultiGeometry>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PolygonError {
+    /// Hole `interiors[hole]` is not inside the exterior ring
+    HoleOutsideExterior { hole: usize },
+    /// Two rings cross. Ring 0 is the exterior, ring `i` is `interiors[i - 1]`.
+    RingsCross { first: usize, second: usize },
+}
+
+#[allow(dead_code)]
+impl Polygon {
+    /// Polygons of a feature's rings, grouped as in [`group_rings`]
+    pub fn from_rings(rings: &[MultiGeometry]) -> Vec<Polygon> {
+        group_rings(rings)
+            .into_iter()
+            .filter_map(|polygon| {
+                let (exterior, interiors) = polygon.split_first()?;
+
+                Some(Polygon {
+                    exterior: (*exterior).clone(),
+                    interiors: interiors.iter().map(|ring| (*ring).clone()).collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Checks that every hole lies inside the exterior and no two rings cross
+    pub fn validate(&self) -> Vec<PolygonError> {
+        let mut errors = Vec::new();
+        let rings: Vec<&MultiGeometry> = std::iter::once(&self.exterior)
+            .chain(self.interiors.iter())
+            .collect();
+
+        for first in 0..rings.len() {
+            for second in first + 1..rings.len() {
+                let cross = rings[first].segments().any(|(a, b)| {
+                    rings[second]
+                        .segments()
+                        .any(|(c, d)| segments_cross(a, b, c, d))
+                });
+
+                if cross {
+                    errors.push(PolygonError::RingsCross { first, second });
+                }
+            }
+        }
+
+        for (hole, interior) in self.interiors.iter().enumerate() {
+            if !interior
+                .iter()
+                .all(|position| self.exterior.contains(position))
+            {
+                errors.push(PolygonError::HoleOutsideExterior { hole });
+            }
+        }
+
+        errors
+    }
+
+    /// Triangle mesh of the polygon by ear clipping, holes are bridged into
+    /// the exterior first. Returns `[lon, lat]` vertices and counter-clockwise
+    /// triangles as indices into them.
+    pub fn triangulate(&self) -> (Vec<[f64; 2]>, Vec<u32>) {
+        let open_ring = |ring: &MultiGeometry, clockwise: bool| {
+            let mut ring = ring.ensure_winding(clockwise);
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            ring
+        };
+
+        let exterior = open_ring(&self.exterior, false);
+        if exterior.len() < 3 {
+            return (Vec::new(), Vec::new());
+        }
+
+        let mut holes: Vec<MultiGeometry> = self
+            .interiors
+            .iter()
+            .map(|ring| open_ring(ring, true))
+            .filter(|ring| ring.len() >= 3)
+            .collect();
+
+        let mut vertices: Vec<Position> = exterior.clone();
+        let mut outline: Vec<usize> = (0..exterior.len()).collect();
+
+        // bridge holes from right to left, so every bridge only has to
+        // cross into rings that are already part of the outline
+        let rightmost = |ring: &MultiGeometry| {
+            (0..ring.len())
+                .max_by(|&a, &b| ring[a].lon.total_cmp(&ring[b].lon))
+                .unwrap_or(0)
+        };
+        holes.sort_by(|a, b| b[rightmost(b)].lon.total_cmp(&a[rightmost(a)].lon));
+
+        for (i, hole) in holes.iter().enumerate() {
+            let offset = vertices.len();
+            vertices.extend(hole.iter().copied());
+
+            let start = rightmost(hole);
+            let from = hole[start];
+
+            let crosses = |to: &Position| {
+                let outline_edges = (0..outline.len()).map(|k| {
+                    (
+                        &vertices[outline[k]],
+                        &vertices[outline[(k + 1) % outline.len()]],
+                    )
+                });
+                let hole_edges = holes[i..].iter().flat_map(|ring| {
+                    (0..ring.len()).map(|k| (&ring[k], &ring[(k + 1) % ring.len()]))
+                });
+
+                outline_edges
+                    .chain(hole_edges)
+                    .any(|(a, b)| segments_cross(&from, to, a, b))
+            };
+
+            let mut candidates: Vec<usize> = (0..outline.len()).collect();
+            candidates.sort_by(|&a, &b| {
+                let distance = |k: usize| {
+                    let p = &vertices[outline[k]];
+                    (p.lon - from.lon).powi(2) + (p.lat - from.lat).powi(2)
+                };
+                distance(a).total_cmp(&distance(b))
+            });
+
+            let bridge = candidates
+                .iter()
+                .copied()
+                .find(|&k| !crosses(&vertices[outline[k]]))
+                .unwrap_or(candidates[0]);
+
+            let mut spliced = Vec::with_capacity(outline.len() + hole.len() + 2);
+            spliced.extend_from_slice(&outline[..=bridge]);
+            spliced.extend((0..=hole.len()).map(|k| offset + (start + k) % hole.len()));
+            spliced.extend_from_slice(&outline[bridge..]);
+            outline = spliced;
+        }
+
+        let cross = |o: &Position, a: &Position, b: &Position| {
+            (a.lon - o.lon) * (b.lat - o.lat) - (a.lat - o.lat) * (b.lon - o.lon)
+        };
+
+        let mut indices = Vec::with_capacity((outline.len() - 2) * 3);
+        let mut missed = 0;
+        let mut i = 0;
+
+        while outline.len() > 3 {
+            let n = outline.len();
+            let (prev, current, next) = (
+                outline[(i + n - 1) % n],
+                outline[i % n],
+                outline[(i + 1) % n],
+            );
+            let (a, b, c) = (&vertices[prev], &vertices[current], &vertices[next]);
+
+            let is_ear = cross(a, b, c) > 0.0
+                && !outline.iter().any(|&k| {
+                    let p = &vertices[k];
+                    p != a
+                        && p != b
+                        && p != c
+                        && cross(a, b, p) >= 0.0
+                        && cross(b, c, p) >= 0.0
+                        && cross(c, a, p) >= 0.0
+                });
+
+            if is_ear {
+                indices.extend([prev as u32, current as u32, next as u32]);
+                outline.remove(i % n);
+                missed = 0;
+            } else if missed >= n {
+                // a ring without ears is degenerate, drop the vertex without
+                // a triangle so clipping terminates
+                outline.remove(i % n);
+                missed = 0;
+            } else {
+                i += 1;
+                missed += 1;
+            }
+
+            i %= outline.len();
+        }
+
+        if let [a, b, c] = outline[..] {
+            if cross(&vertices[a], &vertices[b], &vertices[c]) > 0.0 {
+                indices.extend([a as u32, b as u32, c as u32]);
+            }
+        }
+
+        let vertices = vertices
+            .iter()
+            .map(|position| [position.lon, position.lat])
+            .collect();
+
+        (vertices, indices)
+    }
+}
+
+/// Groups rings into polygons. A ring inside an earlier exterior is a hole
+/// of it, every other ring starts a new polygon.
+pub fn group_rings(rings: &[MultiGeometry]) -> Vec<Vec<&MultiGeometry>> {
+    let mut polygons: Vec<Vec<&MultiGeometry>> = Vec::new();
+
+    for ring in rings {
+        let exterior = ring.first().and_then(|position| {
+            polygons
+                .iter_mut()
+                .find(|polygon| polygon[0].contains(position))
+        });
+
+        match exterior {
+            Some(polygon) => polygon.push(ring),
+            None => polygons.push(vec![ring]),
+        }
+    }
+
+    polygons
+}
+
+/// Whether segments a-b and c-d properly intersect, touching does not count
+fn segments_cross(a: &Position, b: &Position, c: &Position, d: &Position) -> bool {
+    let orientation = |o: &Position, p: &Position, q: &Position| {
+        ((p.lon - o.lon) * (q.lat - o.lat) - (p.lat - o.lat) * (q.lon - o.lon)).signum()
+    };
+
+    let d1 = orientation(c, d, a);
+    let d2 = orientation(c, d, b);
+    let d3 = orientation(a, b, c);
+    let d4 = orientation(a, b, d);
+
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Flat coordinates of several geometries in one buffer.
+/// Geometry `i` spans `coords[offsets[i]..offsets[i + 1]]`, the last one
+/// runs to the end of `coords`.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlatCoords {
+    pub coords: Vec<f64>,
+    pub offsets: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl FlatCoords {
+    pub fn push(&mut self, geometry: &MultiGeometry) {
+        self.offsets.push(self.coords.len());
+        self.coords.extend(geometry.to_flat_coords());
+    }
+}
+
+/// Convex hull of `points` (Andrew's monotone chain) as a closed,
+/// counter-clockwise ring. Fewer than three distinct points are returned
+/// sorted and unclosed, collinear points as a closed ring over the two ends.
+#[allow(dead_code)]
+pub fn convex_hull(mut points: Vec<Position>) -> MultiGeometry {
+    points.sort_by(|a, b| a.lon.total_cmp(&b.lon).then(a.lat.total_cmp(&b.lat)));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross = |o: &Position, a: &Position, b: &Position| {
+        (a.lon - o.lon) * (b.lat - o.lat) - (a.lat - o.lat) * (b.lon - o.lon)
+    };
+
+    let mut hull: MultiGeometry = Vec::with_capacity(points.len() + 1);
+
+    // lower hull, then upper hull
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let start = hull.len();
+        for point in pass {
+            while hull.len() >= start + 2
+                && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], &point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // last point of each chain is the first point of the next one
+        hull.pop();
+    }
+
+    hull.push(hull[0]);
+    hull
+}
+
+fn outcode(position: &Position, bounds: &Rect) -> u8 {
+    let mut code = 0;
+
+    if position.lon < bounds.top_left.lon {
+        code |= OUTCODE_LEFT;
+    } else if position.lon > bounds.bottom_right.lon {
+        code |= OUTCODE_RIGHT;
+    }
+
+    if position.lat < bounds.bottom_right.lat {
+        code |= OUTCODE_BOTTOM;
+    } else if position.lat > bounds.top_left.lat {
+        code |= OUTCODE_TOP;
+    }
+
+    code
+}
+
+fn intersect_lon(a: &Position, b: &Position, lon: f64) -> Position {
+    Position {
+        lat: a.lat + (b.lat - a.lat) * (lon - a.lon) / (b.lon - a.lon),
+        lon,
+    }
+}
+
+fn intersect_lat(a: &Position, b: &Position, lat: f64) -> Position {
+    Position {
+        lat,
+        lon: a.lon + (b.lon - a.lon) * (lat - a.lat) / (b.lat - a.lat),
+    }
+}
+
+fn clip_segment(a: &Position, b: &Position, bounds: &Rect) -> Option<(Position, Position)> {
+    let mut a = *a;
+    let mut b = *b;
+    let mut code_a = outcode(&a, bounds);
+    let mut code_b = outcode(&b, bounds);
+
+    loop {
+        if code_a | code_b == 0 {
+            return Some((a, b));
+        }
+
+        if code_a & code_b != 0 {
+            return None;
+        }
+
+        let code = if code_a != 0 { code_a } else { code_b };
+
+        let clipped = if code & OUTCODE_TOP != 0 {
+            intersect_lat(&a, &b, bounds.top_left.lat)
+        } else if code & OUTCODE_BOTTOM != 0 {
+            intersect_lat(&a, &b, bounds.bottom_right.lat)
+        } else if code & OUTCODE_RIGHT != 0 {
+            intersect_lon(&a, &b, bounds.bottom_right.lon)
+        } else {
+            intersect_lon(&a, &b, bounds.top_left.lon)
+        };
+
+        if code == code_a {
+            a = clipped;
+            code_a = outcode(&a, bounds);
+        } else {
+            b = clipped;
+            code_b = outcode(&b, bounds);
+        }
+    }
+}
+
+fn clip_ring_edge<I, X>(ring: &[Position], inside: I, intersect: X) -> Vec<Position>
+where
+    I: Fn(&Position) -> bool,
+    X: Fn(&Position, &Position) -> Position,
+{
+    let mut output = Vec::with_capacity(ring.len());
+
+    for (i, current) in ring.iter().enumerate() {
+        let previous = &ring[(i + ring.len() - 1) % ring.len()];
+
+        match (inside(previous), inside(current)) {
+            (true, true) => output.push(*current),
+            (true, false) => output.push(intersect(previous, current)),
+            (false, true) => {
+                output.push(intersect(previous, current));
+                output.push(*current);
+            }
+            (false, false) => {}
+        }
+    }
+
+    output
+}
+
+impl MultiGeometryExt for MultiGeometry {
+    fn clip_to(&self, bounds: &Rect) -> Vec<MultiGeometry> {
+        if self.len() == 1 {
+            return if bounds.contains(&self[0]) {
+                vec![self.clone()]
+            } else {
+                Vec::new()
+            };
+        }
+
+        let mut pieces: Vec<MultiGeometry> = Vec::new();
+        let mut current: MultiGeometry = Vec::new();
+
+        for segment in self.windows(2) {
+            match clip_segment(&segment[0], &segment[1], bounds) {
+                Some((start, end)) => {
+                    if current.is_empty() || outcode(&segment[0], bounds) != 0 {
+                        if current.len() > 1 {
+                            pieces.push(std::mem::take(&mut current));
+                        }
+                        current = vec![start];
+                    }
+
+                    current.push(end);
+
+                    if outcode(&segment[1], bounds) != 0 {
+                        pieces.push(std::mem::take(&mut current));
+                    }
+                }
+                None => {
+                    if current.len() > 1 {
+                        pieces.push(std::mem::take(&mut current));
+                    }
+                    current.clear();
+                }
+            }
+        }
+
+        if current.len() > 1 {
+            pieces.push(current);
+        }
+
+        pieces
+    }
+
+    fn clip_polygon_to(&self, bounds: &Rect) -> Option<MultiGeometry> {
+        let closed = self.len() > 1
+            && self[0].lat == self[self.len() - 1].lat
+            && self[0].lon == self[self.len() - 1].lon;
+
+        let mut ring: Vec<Position> = if closed {
+            self[..self.len() - 1].to_vec()
+        } else {
+            self.clone()
+        };
+
+        let west = bounds.top_left.lon;
+        let east = bounds.bottom_right.lon;
+        let south = bounds.bottom_right.lat;
+        let north = bounds.top_left.lat;
+
+        ring = clip_ring_edge(&ring, |p| p.lon >= west, |a, b| intersect_lon(a, b, west));
+        ring = clip_ring_edge(&ring, |p| p.lon <= east, |a, b| intersect_lon(a, b, east));
+        ring = clip_ring_edge(&ring, |p| p.lat >= south, |a, b| intersect_lat(a, b, south));
+        ring = clip_ring_edge(&ring, |p| p.lat <= north, |a, b| intersect_lat(a, b, north));
+
+        if ring.len() < 3 {
+            return None;
+        }
+
+        if closed {
+            ring.push(ring[0]);
+        }
+
+        Some(ring)
+    }
+
+    fn to_flat_coords(&self) -> Vec<f64> {
+        self.iter()
+            .flat_map(|position| [position.lon, position.lat])
+            .collect()
+    }
+
+    fn densify(&self, max_segment_m: f64) -> MultiGeometry {
+        if max_segment_m.is_nan() || max_segment_m <= 0.0 {
+            return self.clone();
+        }
+
+        let mut output: MultiGeometry = Vec::with_capacity(self.len());
+
+        for (start, end) in self.segments() {
+            let steps = (start.distance_to(end) / max_segment_m).ceil().max(1.0) as usize;
+
+            output.push(*start);
+            for step in 1..steps {
+                output.push(start.intermediate(end, step as f64 / steps as f64));
+            }
+        }
+
+        if let Some(last) = self.last() {
+            output.push(*last);
+        }
+
+        output
+    }
+
+    fn signed_area(&self) -> f64 {
+        if self.len() < 3 {
+            return 0.0;
+        }
+
+        let mut area = 0.0;
+
+        for (i, current) in self.iter().enumerate() {
+            let next = &self[(i + 1) % self.len()];
+            area += current.lon * next.lat - next.lon * current.lat;
+        }
+
+        area / 2.0
+    }
+
+    fn area(&self) -> f64 {
+        if self.len() < 3 {
+            return 0.0;
+        }
+
+        let mut sum = 0.0;
+
+        for (i, current) in self.iter().enumerate() {
+            let next = &self[(i + 1) % self.len()];
+            sum += (next.lon - current.lon)
+                * DEGREE
+                * (2.0 + (current.lat * DEGREE).sin() + (next.lat * DEGREE).sin());
+        }
+
+        (sum * WGS84_SEMIMAJOR_AXIS_METERS * WGS84_SEMIMAJOR_AXIS_METERS / 2.0).abs()
+    }
+
+    fn ensure_winding(&self, clockwise: bool) -> MultiGeometry {
+        let area = self.signed_area();
+
+        if area != 0.0 && (area < 0.0) != clockwise {
+            self.iter().rev().copied().collect()
+        } else {
+            self.clone()
+        }
+    }
+
+    fn segments(&self) -> impl Iterator<Item = (&Position, &Position)> {
+        self.windows(2).map(|pair| (&pair[0], &pair[1]))
+    }
+
+    fn contains(&self, position: &Position) -> bool {
+        let mut inside = false;
+
+        for (i, current) in self.iter().enumerate() {
+            let previous = &self[(i + self.len() - 1) % self.len()];
+
+            if (current.lat > position.lat) != (previous.lat > position.lat)
+                && position.lon
+                    < (previous.lon - current.lon) * (position.lat - current.lat)
+                        / (previous.lat - current.lat)
+                        + current.lon
+            {
+                inside = !inside;
+            }
+        }
+
+        inside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(lat: f64, lon: f64) -> Position {
+        Position { lat, lon }
+    }
+
+    fn square(lat: f64, lon: f64, size: f64) -> MultiGeometry {
+        vec![
+            position(lat, lon),
+            position(lat, lon + size),
+            position(lat + size, lon + size),
+            position(lat + size, lon),
+            position(lat, lon),
+        ]
+    }
+
+    #[test]
+    fn rings_are_grouped_by_containment() {
+        let island = square(54.0, 10.0, 0.1);
+        let hole = square(54.02, 10.02, 0.02);
+        let islet = square(54.5, 10.5, 0.1);
+
+        let polygons = Polygon::from_rings(&[island.clone(), islet.clone(), hole.clone()]);
+
+        assert_eq!(
+            polygons,
+            vec![
+                Polygon {
+                    exterior: island,
+                    interiors: vec![hole],
+                },
+                Polygon {
+                    exterior: islet,
+                    interiors: Vec::new(),
+                },
+            ]
+        );
+        assert!(polygons.iter().all(|polygon| polygon.validate().is_empty()));
+    }
+
+    #[test]
+    fn square_is_two_triangles() {
+        let polygon = Polygon {
+            exterior: square(54.0, 10.0, 0.1),
+            interiors: Vec::new(),
+        };
+
+        let (vertices, indices) = polygon.triangulate();
+
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(indices.len(), 6);
+    }
+
+    #[test]
+    fn collinear_ring_has_no_triangles() {
+        let polygon = Polygon {
+            exterior: vec![
+                position(54.0, 10.0),
+                position(54.0, 10.1),
+                position(54.0, 10.2),
+                position(54.0, 10.3),
+            ],
+            interiors: Vec::new(),
+        };
+
+        assert!(polygon.triangulate().1.is_empty());
+    }
+
+    #[test]
+    fn hole_outside_exterior_is_reported() {
+        let polygon = Polygon {
+            exterior: square(54.0, 10.0, 0.1),
+            interiors: vec![square(54.5, 10.5, 0.02)],
+        };
+
+        assert_eq!(
+            polygon.validate(),
+            vec![PolygonError::HoleOutsideExterior { hole: 0 }]
+        );
+    }
+
+    #[test]
+    fn densify_rejects_non_positive_length() {
+        let line = vec![position(54.0, 10.0), position(54.0, 10.1)];
+
+        assert_eq!(line.densify(0.0), line);
+        assert_eq!(line.densify(-5.0), line);
+        assert_eq!(line.densify(f64::NAN), line);
+        assert!(line.densify(1000.0).len() > 2);
+    }
+
+    fn cell() -> Rect {
+        Rect {
+            top_left: position(55.0, 10.0),
+            bottom_right: position(54.0, 11.0),
+        }
+    }
+
+    #[test]
+    fn line_crossing_the_west_edge_is_cut() {
+        let line = vec![position(54.5, 9.5), position(54.5, 10.5)];
+
+        assert_eq!(
+            line.clip_to(&cell()),
+            vec![vec![position(54.5, 10.0), position(54.5, 10.5)]]
+        );
+
+        let inside = vec![position(54.2, 10.2), position(54.8, 10.8)];
+        assert_eq!(inside.clip_to(&cell()), vec![inside.clone()]);
+    }
+
+    #[test]
+    fn polygon_clipping() {
+        assert_eq!(square(56.0, 12.0, 0.1).clip_polygon_to(&cell()), None);
+
+        let ring = square(54.9, 10.9, 0.2).clip_polygon_to(&cell()).unwrap();
+        assert!(ring.iter().all(|p| cell().contains(p)));
+        assert!((ring.area() - square(54.9, 10.9, 0.1).area()).abs() < 1.0);
+    }
+
+    #[test]
+    fn segments_pair_consecutive_points() {
+        let empty: MultiGeometry = Vec::new();
+        assert_eq!(empty.segments().count(), 0);
+
+        let single = vec![position(54.0, 10.0)];
+        assert_eq!(single.segments().count(), 0);
+
+        let ring = square(54.0, 10.0, 0.1);
+        let segments: Vec<_> = ring.segments().collect();
+        assert_eq!(segments.len(), ring.len() - 1);
+        assert_eq!(segments[0], (&ring[0], &ring[1]));
+        assert_eq!(segments[3], (&ring[3], &ring[4]));
+    }
+
+    #[test]
+    fn densified_segments_stay_below_the_limit() {
+        let line = vec![
+            position(54.0, 10.0),
+            position(54.0, 10.1),
+            position(54.05, 10.1),
+        ];
+
+        let dense = line.densify(1000.0);
+
+        assert!(dense.len() > line.len());
+        assert_eq!(dense.first(), line.first());
+        assert_eq!(dense.last(), line.last());
+        assert!(dense.iter().any(|p| *p == line[1]));
+        assert!(dense
+            .segments()
+            .all(|(start, end)| start.distance_to(end) <= 1000.0 + 1e-6));
+    }
+
+    #[test]
+    fn winding_is_reversed_only_when_needed() {
+        // south-west, south-east, north-east: counter-clockwise in lon/lat
+        let ccw = square(54.0, 10.0, 0.1);
+        let cw: MultiGeometry = ccw.iter().rev().copied().collect();
+
+        assert!(ccw.signed_area() > 0.0);
+        assert!(cw.signed_area() < 0.0);
+
+        assert_eq!(ccw.ensure_winding(false), ccw);
+        assert_eq!(ccw.ensure_winding(true), cw);
+        assert_eq!(cw.ensure_winding(true), cw);
+        assert_eq!(cw.ensure_winding(false), ccw);
+    }
+
+    #[test]
+    fn convex_hull_drops_collinear_and_duplicate_points() {
+        let points = vec![
+            position(54.0, 10.0),
+            position(54.0, 10.05),
+            position(54.0, 10.1),
+            position(54.1, 10.1),
+            position(54.1, 10.0),
+            position(54.05, 10.05),
+            position(54.1, 10.1),
+            position(54.0, 10.0),
+        ];
+
+        assert_eq!(
+            convex_hull(points),
+            vec![
+                position(54.0, 10.0),
+                position(54.0, 10.1),
+                position(54.1, 10.1),
+                position(54.1, 10.0),
+                position(54.0, 10.0),
+            ]
+        );
+
+        let collinear = vec![
+            position(54.0, 10.1),
+            position(54.0, 10.0),
+            position(54.0, 10.05),
+        ];
+        assert_eq!(
+            convex_hull(collinear),
+            vec![
+                position(54.0, 10.0),
+                position(54.0, 10.1),
+                position(54.0, 10.0)
+            ]
+        );
+
+        let pair = vec![
+            position(54.0, 10.1),
+            position(54.0, 10.0),
+            position(54.0, 10.1),
+        ];
+        assert_eq!(
+            convex_hull(pair),
+            vec![position(54.0, 10.0), position(54.0, 10.1)]
+        );
+        assert!(convex_hull(Vec::new()).is_empty());
+    }
+}
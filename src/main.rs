@@ -24,8 +24,19 @@ use std::{
 
 use chartfile::ChartFile;
 
+mod attributes;
 mod chartfile;
+mod chartset;
+mod error;
+#[cfg(feature = "geojson")]
+mod geojson;
+mod geometry;
 mod s57;
+mod soundings;
+#[cfg(feature = "svg")]
+mod svg;
+#[cfg(any(test, feature = "test-support"))]
+mod test_support;
 mod types;
 
 fn main() {
@@ -44,15 +55,21 @@ fn main() {
         let file = File::open("/home/silas/Downloads/exported/OC-49-M11SO4.oesu")
             .expect("couldnt open file");
         let mut reader = BufReader::new(file);
-        let result = ChartFile::parse_file(&mut reader);
-        if let Ok(_) = result {
-            println!("successfully read {}", path.file_name().to_str().unwrap());
-        } else {
-            println!(
+        match ChartFile::parse_file(&mut reader) {
+            Ok(chart) => println!(
+                "successfully read {}: {} ed. {} upd. {}, 1:{}, {} features",
+                path.file_name().to_str().unwrap(),
+                chart.name(),
+                chart.edition(),
+                chart.update(),
+                chart.native_scale(),
+                chart.features().len()
+            ),
+            Err(err) => println!(
                 "failed to read {} with err {}",
                 path.file_name().to_str().unwrap(),
-                result.err().unwrap()
-            );
+                err
+            ),
         }
     }
 }
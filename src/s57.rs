@@ -20,16 +20,26 @@ use std::collections::HashMap;
 use std::f64::consts::PI;
 use std::fmt;
 
-#[derive(Debug, Copy, Clone)]
+use crate::{
+    geometry::{group_rings, FlatCoords, MultiGeometryExt, Polygon},
+    types::{FromLeBytes, LeBytes},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub lat: f64,
     pub lon: f64,
 }
 
-const WGS84_SEMIMAJOR_AXIS_METERS: f64 = 6378137.0;
+pub(crate) const WGS84_SEMIMAJOR_AXIS_METERS: f64 = 6378137.0;
 const MERCATOR_K0: f64 = 0.9996;
-const DEGREE: f64 = PI / 180.0;
+/// Angular distance in radians below which two positions count as antipodal.
+/// The haversine distance of exact antipodes is off by about 3e-8.
+const ANTIPODAL_TOLERANCE: f64 = 1e-7;
+pub(crate) const DEGREE: f64 = PI / 180.0;
 
+#[allow(dead_code)]
 impl Position {
     pub fn from_simple_mercator(x: f64, y: f64, reference: &Position) -> Self {
         let z = WGS84_SEMIMAJOR_AXIS_METERS * MERCATOR_K0;
@@ -45,9 +55,94 @@ impl Position {
 
         Self { lat, lon }
     }
+
+    /// Great-circle distance in metres (haversine)
+    pub fn distance_to(&self, other: &Position) -> f64 {
+        let d_lat = (other.lat - self.lat) * DEGREE;
+        let d_lon = (other.lon - self.lon) * DEGREE;
+
+        let a = (d_lat / 2.0).sin().powi(2)
+            + (self.lat * DEGREE).cos() * (other.lat * DEGREE).cos() * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * WGS84_SEMIMAJOR_AXIS_METERS * a.sqrt().asin()
+    }
+
+    /// Point at `fraction` of the great-circle path towards `other`.
+    /// Antipodal positions have no unique path, the result is NaN.
+    pub fn intermediate(&self, other: &Position, fraction: f64) -> Position {
+        let delta = self.distance_to(other) / WGS84_SEMIMAJOR_AXIS_METERS;
+
+        if delta == 0.0 {
+            return *self;
+        }
+
+        if PI - delta < ANTIPODAL_TOLERANCE {
+            return Position {
+                lat: f64::NAN,
+                lon: f64::NAN,
+            };
+        }
+
+        let (lat1, lon1) = (self.lat * DEGREE, self.lon * DEGREE);
+        let (lat2, lon2) = (other.lat * DEGREE, other.lon * DEGREE);
+
+        let a = ((1.0 - fraction) * delta).sin() / delta.sin();
+        let b = (fraction * delta).sin() / delta.sin();
+
+        let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+        let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+        let z = a * lat1.sin() + b * lat2.sin();
+
+        Position {
+            lat: z.atan2((x * x + y * y).sqrt()) / DEGREE,
+            lon: y.atan2(x) / DEGREE,
+        }
+    }
+
+    /// `n` evenly spaced great-circle points from `self` to `other`, both included.
+    /// NaN for antipodal positions, see [`Position::intermediate`].
+    pub fn great_circle_points(&self, other: &Position, n: usize) -> Vec<Position> {
+        match n {
+            0 => Vec::new(),
+            1 => vec![*self],
+            _ => (0..n)
+                .map(|i| self.intermediate(other, i as f64 / (n - 1) as f64))
+                .collect(),
+        }
+    }
+
+    /// Point `distance_m` metres away along the initial `bearing` (degrees from north)
+    pub fn destination(&self, bearing: f64, distance_m: f64) -> Position {
+        let delta = distance_m / WGS84_SEMIMAJOR_AXIS_METERS;
+        let bearing = bearing * DEGREE;
+        let (lat1, lon1) = (self.lat * DEGREE, self.lon * DEGREE);
+
+        let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * bearing.cos()).asin();
+        let lon2 = lon1
+            + (bearing.sin() * delta.sin() * lat1.cos())
+                .atan2(delta.cos() - lat1.sin() * lat2.sin());
+
+        Position {
+            lat: lat2 / DEGREE,
+            lon: lon2 / DEGREE,
+        }
+    }
+
+    /// Arc of radius `radius_m` around `self` from `start` to `end` bearing
+    /// (degrees, clockwise), one vertex every `step` degrees
+    pub fn arc(&self, radius_m: f64, start: f64, end: f64, step: f64) -> MultiGeometry {
+        let sweep = (end - start).rem_euclid(360.0);
+        let sweep = if sweep == 0.0 { 360.0 } else { sweep };
+        let steps = (sweep / step.max(f64::EPSILON)).ceil().max(1.0) as usize;
+
+        (0..=steps)
+            .map(|i| self.destination(start + sweep * i as f64 / steps as f64, radius_m))
+            .collect()
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub top_left: Position,
     pub bottom_right: Position,
@@ -55,21 +150,71 @@ pub struct Rect {
 
 impl Rect {
     pub fn center(&self) -> Position {
-        return Position {
+        Position {
             lat: (self.top_left.lat + self.bottom_right.lat) / 2.0,
             lon: (self.top_left.lon + self.bottom_right.lon) / 2.0,
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.lat <= self.top_left.lat
+            && position.lat >= self.bottom_right.lat
+            && position.lon >= self.top_left.lon
+            && position.lon <= self.bottom_right.lon
+    }
+
+    /// Distance in metres from `position` to the nearest point on the edge
+    /// of the rectangle, for positions inside as well as outside
+    #[allow(dead_code)]
+    pub fn distance_to_boundary(&self, position: &Position) -> f64 {
+        let north = self.top_left.lat;
+        let south = self.bottom_right.lat;
+        let west = self.top_left.lon;
+        let east = self.bottom_right.lon;
+
+        let clamped = Position {
+            lat: position.lat.clamp(south.min(north), north.max(south)),
+            lon: position.lon.clamp(west.min(east), east.max(west)),
         };
+
+        if !self.contains(position) {
+            return position.distance_to(&clamped);
+        }
+
+        [
+            Position {
+                lat: north,
+                ..*position
+            },
+            Position {
+                lat: south,
+                ..*position
+            },
+            Position {
+                lon: west,
+                ..*position
+            },
+            Position {
+                lon: east,
+                ..*position
+            },
+        ]
+        .iter()
+        .map(|edge| position.distance_to(edge))
+        .fold(f64::INFINITY, f64::min)
     }
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Forward,
     Reverse,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineElement {
     start_connected_node: u32,
     edge_vector: u32,
@@ -77,8 +222,38 @@ pub struct LineElement {
     direction: Direction,
 }
 
+impl LineElement {
+    pub fn start_connected_node(&self) -> u32 {
+        self.start_connected_node
+    }
+
+    pub fn end_connected_node(&self) -> u32 {
+        self.end_connected_node
+    }
+}
+
+impl FromLeBytes for LineElement {
+    const SIZE: usize = 16;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        LineElement {
+            start_connected_node: bytes.u32(),
+            edge_vector: bytes.u32(),
+            end_connected_node: bytes.u32(),
+            // S-57 ORNT codes reverse as 2, some encoders write -1
+            direction: match bytes.i32() {
+                2 | -1 => Direction::Reverse,
+                _ => Direction::Forward,
+            },
+        }
+    }
+}
+
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointGeometry {
     pub position: Position,
     pub value: f64,
@@ -86,16 +261,151 @@ pub struct PointGeometry {
 
 pub type MultiGeometry = Vec<Position>;
 
+/// Reference of a line element that is missing from the vector tables
 #[allow(dead_code)]
-#[derive(Clone, Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GeometryError {
+    MissingConnectedNode(u32),
+    MissingEdge(u32),
+}
+
+/// Appends `position` unless it repeats the last vertex
+fn push_vertex(geometry: &mut MultiGeometry, position: Position) {
+    if geometry.last() != Some(&position) {
+        geometry.push(position);
+    }
+}
+
+/// Chains line elements into geometries. A new geometry starts whenever an
+/// element doesn't continue at the end node of its predecessor, and with
+/// `closed` also after an element returns to the start node of the ring.
+fn chain_elements(
+    elements: &[LineElement],
+    edges: &HashMap<u32, VectorEdge>,
+    nodes: &HashMap<u32, ConnectedNode>,
+    closed: bool,
+    errors: &mut Vec<GeometryError>,
+) -> Vec<MultiGeometry> {
+    let mut geometries = Vec::new();
+    let mut current: MultiGeometry = Vec::new();
+    let mut first_node: Option<u32> = None;
+    let mut last_node: Option<u32> = None;
+
+    let node = |index: u32, errors: &mut Vec<GeometryError>| match nodes.get(&index) {
+        Some(node) => Some(*node.position()),
+        None => {
+            errors.push(GeometryError::MissingConnectedNode(index));
+            None
+        }
+    };
+
+    for element in elements {
+        if last_node.is_some() && last_node != Some(element.start_connected_node) {
+            if current.len() >= 2 {
+                geometries.push(std::mem::take(&mut current));
+            }
+            current.clear();
+            first_node = None;
+        }
+
+        if first_node.is_none() {
+            first_node = Some(element.start_connected_node);
+        }
+
+        if let Some(position) = node(element.start_connected_node, errors) {
+            push_vertex(&mut current, position);
+        }
+
+        match edges.get(&element.edge_vector) {
+            Some(edge) if element.direction == Direction::Forward => {
+                for position in edge.positions() {
+                    push_vertex(&mut current, *position);
+                }
+            }
+            Some(edge) => {
+                for position in edge.positions().iter().rev() {
+                    push_vertex(&mut current, *position);
+                }
+            }
+            None => errors.push(GeometryError::MissingEdge(element.edge_vector)),
+        }
+
+        if let Some(position) = node(element.end_connected_node, errors) {
+            push_vertex(&mut current, position);
+        }
+
+        last_node = Some(element.end_connected_node);
+
+        if closed && first_node == last_node {
+            if current.len() >= 2 {
+                geometries.push(std::mem::take(&mut current));
+            }
+            current.clear();
+            first_node = None;
+            last_node = None;
+        }
+    }
+
+    if current.len() >= 2 {
+        geometries.push(current);
+    }
+
+    geometries
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum AttributeValue {
     UInt32(u32),
     Double(f64),
     String(String),
 }
 
+#[allow(dead_code)]
+impl AttributeValue {
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            AttributeValue::UInt32(value) => Some(*value),
+            AttributeValue::Double(_) => None,
+            AttributeValue::String(value) => value.trim().parse().ok(),
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AttributeValue::UInt32(value) => Some(*value as f64),
+            AttributeValue::Double(value) => Some(*value),
+            AttributeValue::String(value) => value.trim().parse().ok(),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttributeValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// List-valued attributes (e.g. COLOUR, CATROS) are stored as a
+    /// comma separated string of codes
+    pub fn as_list(&self) -> Vec<u32> {
+        match self {
+            AttributeValue::UInt32(value) => vec![*value],
+            AttributeValue::Double(_) => Vec::new(),
+            AttributeValue::String(value) => value
+                .split(',')
+                .filter_map(|code| code.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VectorEdge {
     points: Vec<Position>,
 }
@@ -125,6 +435,7 @@ impl VectorEdge {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConnectedNode {
     position: Position,
 }
@@ -138,16 +449,28 @@ impl ConnectedNode {
     pub fn position(&self) -> &Position {
         &self.position
     }
+
+    pub fn set_position(&mut self, position: Position) {
+        self.position = position;
+    }
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct S57 {
     s57_type: S57Type,
+    feature_id: u16,
+    primitive: GeometryPrimitive,
+    file_offset: Option<u64>,
+    /// Extent declared by the line, area or multipoint geometry record
+    extent: Option<Rect>,
     line_elements: Vec<LineElement>,
     polygon_line_elements: Vec<LineElement>,
     lines: Vec<MultiGeometry>,
     polygons: Vec<MultiGeometry>,
+    /// Set when line or polygon elements changed after `lines`/`polygons` were built
+    geometry_stale: bool,
     multi_point_geometry: Vec<PointGeometry>,
     point_geometry: Option<Position>,
     attributes: HashMap<S57Attribute, AttributeValue>,
@@ -165,6 +488,11 @@ impl S57 {
             multi_point_geometry: Vec::new(),
             point_geometry: None,
             attributes: HashMap::new(),
+            feature_id: 0,
+            primitive: GeometryPrimitive::Unknown,
+            geometry_stale: false,
+            file_offset: None,
+            extent: None,
         }
     }
 
@@ -178,9 +506,66 @@ impl S57 {
             multi_point_geometry: Vec::new(),
             point_geometry: None,
             attributes: HashMap::new(),
+            feature_id: 0,
+            primitive: GeometryPrimitive::Unknown,
+            geometry_stale: false,
+            file_offset: None,
+            extent: None,
         }
     }
 
+    pub fn set_feature_id(&mut self, feature_id: u16) {
+        self.feature_id = feature_id;
+    }
+
+    /// Id from the FEATURE_ID record. OSENC carries no pointers between
+    /// features, so the members of collection objects (C_AGGR, C_ASSO,
+    /// C_STAC) can't be resolved.
+    pub fn feature_id(&self) -> u16 {
+        self.feature_id
+    }
+
+    pub fn set_primitive(&mut self, primitive: GeometryPrimitive) {
+        self.primitive = primitive;
+    }
+
+    /// Primitive declared by the feature identification record
+    pub fn primitive(&self) -> GeometryPrimitive {
+        self.primitive
+    }
+
+    /// Whether geometry of the given primitive is populated, either
+    /// resolved or as unresolved edge references
+    pub fn has_geometry(&self, primitive: GeometryPrimitive) -> bool {
+        match primitive {
+            GeometryPrimitive::Point => self.point_geometry.is_some(),
+            GeometryPrimitive::Multipoint => !self.multi_point_geometry.is_empty(),
+            GeometryPrimitive::Line => !self.lines.is_empty() || !self.line_elements.is_empty(),
+            GeometryPrimitive::Area => {
+                !self.polygons.is_empty() || !self.polygon_line_elements.is_empty()
+            }
+            GeometryPrimitive::Unknown => false,
+        }
+    }
+
+    pub fn set_file_offset(&mut self, offset: Option<u64>) {
+        self.file_offset = offset;
+    }
+
+    /// Byte offset of the FEATURE_ID record, see `ParseOptions::record_offsets`
+    pub fn file_offset(&self) -> Option<u64> {
+        self.file_offset
+    }
+
+    pub fn set_extent(&mut self, extent: Rect) {
+        self.extent = Some(extent);
+    }
+
+    /// Extent declared by the geometry record, `None` for point features
+    pub fn extent(&self) -> Option<&Rect> {
+        self.extent.as_ref()
+    }
+
     pub fn set_attribute(&mut self, attribute: S57Attribute, value: AttributeValue) {
         self.attributes.insert(attribute, value);
     }
@@ -195,10 +580,62 @@ impl S57 {
 
     pub fn set_line_geometry(&mut self, elements: &[LineElement]) {
         self.line_elements = elements.to_vec();
+        self.geometry_stale = true;
+    }
+
+    /// Edge references of the line geometry, see [`S57::lines`] for the
+    /// resolved positions
+    pub fn line_elements(&self) -> &[LineElement] {
+        &self.line_elements
     }
 
     pub fn set_polygon_geometry(&mut self, elements: &[LineElement]) {
         self.polygon_line_elements = elements.to_vec();
+        self.geometry_stale = true;
+    }
+
+    /// Stores resolved line geometry, marking the cache fresh
+    pub fn set_lines(&mut self, lines: Vec<MultiGeometry>) {
+        self.lines = lines;
+        self.geometry_stale = false;
+    }
+
+    /// Stores resolved polygon geometry, marking the cache fresh
+    pub fn set_polygons(&mut self, polygons: Vec<MultiGeometry>) {
+        self.polygons = polygons;
+        self.geometry_stale = false;
+    }
+
+    /// Resolves the line and polygon elements against the vector tables
+    /// into `lines` and `polygons`. Missing table entries are skipped and
+    /// returned.
+    pub fn build_geometry(
+        &mut self,
+        edges: &HashMap<u32, VectorEdge>,
+        nodes: &HashMap<u32, ConnectedNode>,
+    ) -> Vec<GeometryError> {
+        let mut errors = Vec::new();
+
+        self.lines = chain_elements(&self.line_elements, edges, nodes, false, &mut errors);
+        self.polygons =
+            chain_elements(&self.polygon_line_elements, edges, nodes, true, &mut errors);
+        self.geometry_stale = false;
+
+        errors
+    }
+
+    /// Marks `lines`/`polygons` as outdated, e.g. after the nodes or edges
+    /// they were built from moved
+    pub fn invalidate_geometry(&mut self) {
+        if !self.line_elements.is_empty() || !self.polygon_line_elements.is_empty() {
+            self.geometry_stale = true;
+        }
+    }
+
+    /// Whether `lines`/`polygons` don't reflect the current line and polygon
+    /// elements yet and need [`S57::build_geometry`]
+    pub fn is_geometry_stale(&self) -> bool {
+        self.geometry_stale
     }
 
     pub fn set_point_geometry(&mut self, position: Position) {
@@ -217,17 +654,393 @@ impl S57 {
         &self.multi_point_geometry
     }
 
+    /// Polygon rings as last built, see [`S57::is_geometry_stale`]
     pub fn polygons(&self) -> &Vec<MultiGeometry> {
         &self.polygons
     }
 
+    /// Lines as last built, see [`S57::is_geometry_stale`]
     pub fn lines(&self) -> &Vec<MultiGeometry> {
         &self.lines
     }
 
+    pub fn lines_mut(&mut self) -> &mut Vec<MultiGeometry> {
+        &mut self.lines
+    }
+
     pub fn s57_type(&self) -> S57Type {
         self.s57_type
     }
+
+    /// Flat coordinates of all geometries, see [`FlatCoords`]: the point,
+    /// then all soundings/multipoints as one geometry (without depths), then
+    /// each line and each polygon ring
+    pub fn flat_coords(&self) -> FlatCoords {
+        let mut flat = FlatCoords::default();
+
+        if let Some(position) = self.point_geometry {
+            flat.push(&vec![position]);
+        }
+
+        if !self.multi_point_geometry.is_empty() {
+            flat.push(
+                &self
+                    .multi_point_geometry
+                    .iter()
+                    .map(|point| point.position)
+                    .collect(),
+            );
+        }
+
+        for geometry in self.lines.iter().chain(self.polygons.iter()) {
+            flat.push(geometry);
+        }
+
+        flat
+    }
+
+    /// Copy of the feature with all geometry clipped to `bounds`
+    pub fn clipped(&self, bounds: &Rect) -> S57 {
+        let mut s57 = self.clone();
+
+        s57.lines = self
+            .lines
+            .iter()
+            .flat_map(|line| line.clip_to(bounds))
+            .collect();
+        s57.polygons = self
+            .polygons
+            .iter()
+            .filter_map(|polygon| polygon.clip_polygon_to(bounds))
+            .collect();
+        s57.multi_point_geometry
+            .retain(|point| bounds.contains(&point.position));
+        s57.point_geometry = self
+            .point_geometry
+            .filter(|position| bounds.contains(position));
+
+        s57
+    }
+
+    /// Same object class, attributes and geometry, ignoring feature id and file offset
+    pub fn same_content(&self, other: &S57) -> bool {
+        self.s57_type == other.s57_type
+            && self.attributes == other.attributes
+            && self.point_geometry == other.point_geometry
+            && self.multi_point_geometry == other.multi_point_geometry
+            && self.line_elements == other.line_elements
+            && self.polygon_line_elements == other.polygon_line_elements
+            && self.lines == other.lines
+            && self.polygons == other.polygons
+    }
+
+    /// Mean of all positions of the feature, `None` without geometry
+    pub fn centroid(&self) -> Option<Position> {
+        let (count, lat, lon) = self
+            .positions()
+            .fold((0usize, 0.0, 0.0), |(count, lat, lon), p| {
+                (count + 1, lat + p.lat, lon + p.lon)
+            });
+
+        if count == 0 {
+            return None;
+        }
+
+        Some(Position {
+            lat: lat / count as f64,
+            lon: lon / count as f64,
+        })
+    }
+
+    /// Closest point on any of the feature's geometries to `position` and
+    /// its distance in metres. Segments are treated as straight lines in
+    /// an equirectangular projection around `position`.
+    pub fn nearest_geometry_point(&self, position: &Position) -> Option<(Position, f64)> {
+        let scale = (position.lat * DEGREE).cos();
+
+        let nearest_on_segment = |a: &Position, b: &Position| {
+            let (ax, ay) = ((a.lon - position.lon) * scale, a.lat - position.lat);
+            let (bx, by) = ((b.lon - position.lon) * scale, b.lat - position.lat);
+            let (dx, dy) = (bx - ax, by - ay);
+            let length = dx * dx + dy * dy;
+
+            let t = if length == 0.0 {
+                0.0
+            } else {
+                (-(ax * dx + ay * dy) / length).clamp(0.0, 1.0)
+            };
+
+            Position {
+                lat: a.lat + t * (b.lat - a.lat),
+                lon: a.lon + t * (b.lon - a.lon),
+            }
+        };
+
+        let points = self
+            .point_geometry
+            .iter()
+            .copied()
+            .chain(self.multi_point_geometry.iter().map(|point| point.position));
+
+        let on_segments = self
+            .lines
+            .iter()
+            .chain(self.polygons.iter())
+            .flat_map(|geometry| {
+                geometry
+                    .segments()
+                    .map(|(a, b)| nearest_on_segment(a, b))
+                    // single vertex geometries have no segments
+                    .chain((geometry.len() == 1).then(|| geometry[0]))
+                    .collect::<Vec<_>>()
+            });
+
+        points
+            .chain(on_segments)
+            .map(|nearest| (nearest, position.distance_to(&nearest)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Anchor for a label (e.g. OBJNAM) and how to place the text there.
+    /// Lines are labelled at half length of the longest line, areas at
+    /// their centroid.
+    pub fn label_anchor(&self) -> Option<(Position, LabelPlacement)> {
+        if let Some(position) = self.point_geometry {
+            return Some((position, LabelPlacement::AboveRight));
+        }
+
+        if !self.multi_point_geometry.is_empty() {
+            return Some((
+                self.multi_point_geometry[0].position,
+                LabelPlacement::AboveRight,
+            ));
+        }
+
+        let length =
+            |line: &MultiGeometry| -> f64 { line.segments().map(|(a, b)| a.distance_to(b)).sum() };
+
+        if let Some(line) = self
+            .lines
+            .iter()
+            .filter(|line| line.len() >= 2)
+            .max_by(|a, b| length(a).total_cmp(&length(b)))
+        {
+            let mut remaining = length(line) / 2.0;
+            let last = line.len() - 2;
+
+            for (index, (a, b)) in line.segments().enumerate() {
+                let segment = a.distance_to(b);
+                if remaining <= segment || index == last {
+                    let fraction = if segment > 0.0 {
+                        (remaining / segment).min(1.0)
+                    } else {
+                        0.0
+                    };
+                    let mut angle = (b.lat - a.lat).atan2(b.lon - a.lon).to_degrees();
+                    // keep text upright
+                    if angle > 90.0 {
+                        angle -= 180.0;
+                    } else if angle < -90.0 {
+                        angle += 180.0;
+                    }
+
+                    return Some((
+                        a.intermediate(b, fraction),
+                        LabelPlacement::AlongLine { angle },
+                    ));
+                }
+                remaining -= segment;
+            }
+        }
+
+        self.centroid()
+            .map(|position| (position, LabelPlacement::Centered))
+    }
+
+    /// Polygon rings grouped into exteriors and their holes, see
+    /// [`Polygon::from_rings`]
+    pub fn structured_polygons(&self) -> Vec<Polygon> {
+        Polygon::from_rings(&self.polygons)
+    }
+
+    /// Area in square metres. Rings are grouped into polygons as in
+    /// [`group_rings`], each polygon counts its exterior minus its holes.
+    pub fn area(&self) -> f64 {
+        group_rings(&self.polygons)
+            .iter()
+            .filter_map(|polygon| polygon.split_first())
+            .map(|(exterior, holes)| {
+                (exterior.area() - holes.iter().map(|hole| hole.area()).sum::<f64>()).max(0.0)
+            })
+            .sum()
+    }
+
+    /// All vertices of the feature, regardless of primitive
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.point_geometry
+            .iter()
+            .chain(
+                self.multi_point_geometry
+                    .iter()
+                    .map(|point| &point.position),
+            )
+            .chain(self.lines.iter().flatten())
+            .chain(self.polygons.iter().flatten())
+    }
+
+    /// S-52 style display priority, lower values are drawn first.
+    /// Meta and collection objects are not drawn and get 0.
+    pub fn display_priority(&self) -> u8 {
+        match self.s57_type {
+            // opaque area fills
+            S57Type::DEPARE | S57Type::DRGARE | S57Type::LNDARE | S57Type::UNSARE => 1,
+            // other area fills
+            S57Type::BUAARE
+            | S57Type::LAKARE
+            | S57Type::RIVERS
+            | S57Type::CANALS
+            | S57Type::DOCARE
+            | S57Type::LOKBSN
+            | S57Type::DRYDOC
+            | S57Type::FLODOC
+            | S57Type::PONTON
+            | S57Type::HULKES
+            | S57Type::ICEARE
+            | S57Type::SEAARE
+            | S57Type::LNDRGN
+            | S57Type::VEGATN => 2,
+            // area symbolization: zones, routeing and regulated areas
+            S57Type::ACHARE
+            | S57Type::ACHBRT
+            | S57Type::RESARE
+            | S57Type::CTNARE
+            | S57Type::MIPARE
+            | S57Type::PRCARE
+            | S57Type::FAIRWY
+            | S57Type::TSSLPT
+            | S57Type::TSEZNE
+            | S57Type::TSSRON
+            | S57Type::TSSCRS
+            | S57Type::ISTZNE
+            | S57Type::DWRTPT
+            | S57Type::TWRTPT
+            | S57Type::CBLARE
+            | S57Type::PIPARE
+            | S57Type::DMPGRD
+            | S57Type::SPLARE
+            | S57Type::AIRARE
+            | S57Type::HRBARE
+            | S57Type::ADMARE
+            | S57Type::EXEZNE
+            | S57Type::TESARE
+            | S57Type::CUSZNE
+            | S57Type::CONZNE
+            | S57Type::COSARE
+            | S57Type::FSHZNE
+            | S57Type::FSHGRD
+            | S57Type::MARCUL
+            | S57Type::SBDARE
+            | S57Type::SNDWAV => 3,
+            // depth contours and coastline
+            S57Type::DEPCNT
+            | S57Type::COALNE
+            | S57Type::SLCONS
+            | S57Type::LAKSHR
+            | S57Type::RIVBNK
+            | S57Type::CANBNK
+            | S57Type::DYKCON
+            | S57Type::DAMCON
+            | S57Type::GATCON => 4,
+            // lines and structures
+            S57Type::NAVLNE
+            | S57Type::RECTRC
+            | S57Type::RCRTCL
+            | S57Type::DWRTCL
+            | S57Type::TSELNE
+            | S57Type::TSSBND
+            | S57Type::FERYRT
+            | S57Type::RADLNE
+            | S57Type::CBLSUB
+            | S57Type::CBLOHD
+            | S57Type::PIPSOL
+            | S57Type::PIPOHD
+            | S57Type::BRIDGE
+            | S57Type::CAUSWY
+            | S57Type::TUNNEL
+            | S57Type::ROADWY
+            | S57Type::RAILWY
+            | S57Type::RUNWAY
+            | S57Type::FNCLNE
+            | S57Type::CONVYR
+            | S57Type::OILBAR
+            | S57Type::BERTHS => 5,
+            // dangers and soundings
+            S57Type::SOUNDG
+            | S57Type::WRECKS
+            | S57Type::OBSTRN
+            | S57Type::UWTROC
+            | S57Type::WATTUR
+            | S57Type::CURENT
+            | S57Type::TIDEWY => 6,
+            // aids to navigation
+            S57Type::BCNCAR
+            | S57Type::BCNISD
+            | S57Type::BCNLAT
+            | S57Type::BCNSAW
+            | S57Type::BCNSPP
+            | S57Type::BOYCAR
+            | S57Type::BOYINB
+            | S57Type::BOYISD
+            | S57Type::BOYLAT
+            | S57Type::BOYSAW
+            | S57Type::BOYSPP
+            | S57Type::LITFLT
+            | S57Type::LITVES
+            | S57Type::DAYMAR
+            | S57Type::LNDMRK
+            | S57Type::PILPNT
+            | S57Type::MORFAC => 7,
+            // lights and signals on top of their structures
+            S57Type::LIGHTS
+            | S57Type::TOPMAR
+            | S57Type::FOGSIG
+            | S57Type::RTPBCN
+            | S57Type::RADSTA
+            | S57Type::RETRFL
+            | S57Type::RDOSTA => 8,
+            // cartographic objects
+            S57Type::TEXTS | S57Type::CSYMB | S57Type::COMPS => 9,
+            S57Type::M_ACCY
+            | S57Type::M_CSCL
+            | S57Type::M_COVR
+            | S57Type::M_HDAT
+            | S57Type::M_HOPA
+            | S57Type::M_NPUB
+            | S57Type::M_NSYS
+            | S57Type::M_PROD
+            | S57Type::M_QUAL
+            | S57Type::M_SDAT
+            | S57Type::M_SREL
+            | S57Type::M_UNIT
+            | S57Type::M_VDAT
+            | S57Type::C_AGGR
+            | S57Type::C_ASSO
+            | S57Type::C_STAC => 0,
+            _ => 5,
+        }
+    }
+
+    /// Total number of positions across all geometries of the feature
+    pub fn vertex_count(&self) -> usize {
+        self.lines.iter().map(|line| line.len()).sum::<usize>()
+            + self
+                .polygons
+                .iter()
+                .map(|polygon| polygon.len())
+                .sum::<usize>()
+            + self.multi_point_geometry.len()
+            + usize::from(self.point_geometry.is_some())
+    }
 }
 
 impl fmt::Display for LineElement {
@@ -247,8 +1060,49 @@ impl fmt::Display for LineElement {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
-#[allow(dead_code, non_camel_case_types)]
+/// Attribute value types of the S-57 catalogue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum AttributeKind {
+    Unknown,
+    /// E, a single code
+    Enumerated,
+    /// L, comma separated codes
+    List,
+    /// F
+    Float,
+    /// I
+    Integer,
+    /// A, a string in a fixed format (dates, codes)
+    CodedString,
+    /// S
+    FreeText,
+}
+
+#[allow(dead_code)]
+impl AttributeKind {
+    /// Whether `value` is stored the way this kind is encoded
+    pub fn accepts(&self, value: &AttributeValue) -> bool {
+        match (self, value) {
+            (AttributeKind::Unknown, _) => true,
+            (AttributeKind::Enumerated | AttributeKind::Integer, AttributeValue::UInt32(_)) => true,
+            (AttributeKind::Float, AttributeValue::Double(_)) => true,
+            (AttributeKind::List, AttributeValue::UInt32(_)) => true,
+            (AttributeKind::List, AttributeValue::String(list)) => list
+                .trim_end_matches('\0')
+                .split(',')
+                .all(|code| code.trim().parse::<u32>().is_ok()),
+            (AttributeKind::CodedString | AttributeKind::FreeText, AttributeValue::String(_)) => {
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq, PartialOrd, Ord)]
+#[allow(dead_code, non_camel_case_types, clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum S57Attribute {
     Unknown = 0,
     /// Agency responsible for production
@@ -833,10 +1687,812 @@ impl S57Attribute {
             _ => S57Attribute::Unknown,
         }
     }
+
+    /// Six letter attribute code, e.g. "DRVAL1"
+    pub fn acronym(&self) -> &'static str {
+        match self {
+            S57Attribute::Unknown => "Unknown",
+            S57Attribute::AGENCY => "AGENCY",
+            S57Attribute::BCNSHP => "BCNSHP",
+            S57Attribute::BUISHP => "BUISHP",
+            S57Attribute::BOYSHP => "BOYSHP",
+            S57Attribute::BURDEP => "BURDEP",
+            S57Attribute::CALSGN => "CALSGN",
+            S57Attribute::CATAIR => "CATAIR",
+            S57Attribute::CATACH => "CATACH",
+            S57Attribute::CATBRG => "CATBRG",
+            S57Attribute::CATBUA => "CATBUA",
+            S57Attribute::CATCBL => "CATCBL",
+            S57Attribute::CATCAN => "CATCAN",
+            S57Attribute::CATCAM => "CATCAM",
+            S57Attribute::CATCHP => "CATCHP",
+            S57Attribute::CATCOA => "CATCOA",
+            S57Attribute::CATCTR => "CATCTR",
+            S57Attribute::CATCON => "CATCON",
+            S57Attribute::CATCOV => "CATCOV",
+            S57Attribute::CATCRN => "CATCRN",
+            S57Attribute::CATDAM => "CATDAM",
+            S57Attribute::CATDIS => "CATDIS",
+            S57Attribute::CATDOC => "CATDOC",
+            S57Attribute::CATDPG => "CATDPG",
+            S57Attribute::CATFNC => "CATFNC",
+            S57Attribute::CATFRY => "CATFRY",
+            S57Attribute::CATFIF => "CATFIF",
+            S57Attribute::CATFOG => "CATFOG",
+            S57Attribute::CATFOR => "CATFOR",
+            S57Attribute::CATGAT => "CATGAT",
+            S57Attribute::CATICE => "CATICE",
+            S57Attribute::CATINB => "CATINB",
+            S57Attribute::CATLND => "CATLND",
+            S57Attribute::CATLMK => "CATLMK",
+            S57Attribute::CATLAM => "CATLAM",
+            S57Attribute::CATLIT => "CATLIT",
+            S57Attribute::CATMFA => "CATMFA",
+            S57Attribute::CATMPA => "CATMPA",
+            S57Attribute::CATMOR => "CATMOR",
+            S57Attribute::CATOBS => "CATOBS",
+            S57Attribute::CATOFP => "CATOFP",
+            S57Attribute::CATOLB => "CATOLB",
+            S57Attribute::CATPLE => "CATPLE",
+            S57Attribute::CATPIL => "CATPIL",
+            S57Attribute::CATPIP => "CATPIP",
+            S57Attribute::CATPRA => "CATPRA",
+            S57Attribute::CATPYL => "CATPYL",
+            S57Attribute::CATQUA => "CATQUA",
+            S57Attribute::CATRAS => "CATRAS",
+            S57Attribute::CATRTB => "CATRTB",
+            S57Attribute::CATROS => "CATROS",
+            S57Attribute::CATTRK => "CATTRK",
+            S57Attribute::CATRSC => "CATRSC",
+            S57Attribute::CATREA => "CATREA",
+            S57Attribute::CATROD => "CATROD",
+            S57Attribute::CATRUN => "CATRUN",
+            S57Attribute::CATSEA => "CATSEA",
+            S57Attribute::CATSLC => "CATSLC",
+            S57Attribute::CATSIT => "CATSIT",
+            S57Attribute::CATSIW => "CATSIW",
+            S57Attribute::CATSIL => "CATSIL",
+            S57Attribute::CATSLO => "CATSLO",
+            S57Attribute::CATSCF => "CATSCF",
+            S57Attribute::CATSPM => "CATSPM",
+            S57Attribute::CAT_TS => "CAT_TS",
+            S57Attribute::CATTSS => "CATTSS",
+            S57Attribute::CATVEG => "CATVEG",
+            S57Attribute::CATWAT => "CATWAT",
+            S57Attribute::CATWED => "CATWED",
+            S57Attribute::CATWRK => "CATWRK",
+            S57Attribute::SPACE => "SPACE",
+            S57Attribute::CHARS => "CHARS",
+            S57Attribute::COLOUR => "COLOUR",
+            S57Attribute::COLPAT => "COLPAT",
+            S57Attribute::COMCHA => "COMCHA",
+            S57Attribute::CSIZE => "CSIZE",
+            S57Attribute::CPDATE => "CPDATE",
+            S57Attribute::CSCALE => "CSCALE",
+            S57Attribute::CONDTN => "CONDTN",
+            S57Attribute::CONRAD => "CONRAD",
+            S57Attribute::CONVIS => "CONVIS",
+            S57Attribute::CURVEL => "CURVEL",
+            S57Attribute::DATEND => "DATEND",
+            S57Attribute::DATSTA => "DATSTA",
+            S57Attribute::DRVAL1 => "DRVAL1",
+            S57Attribute::DRVAL2 => "DRVAL2",
+            S57Attribute::DUNITS => "DUNITS",
+            S57Attribute::ELEVAT => "ELEVAT",
+            S57Attribute::ESTRNG => "ESTRNG",
+            S57Attribute::EXPSOU => "EXPSOU",
+            S57Attribute::FUNCTN => "FUNCTN",
+            S57Attribute::HEIGHT => "HEIGHT",
+            S57Attribute::HUNITS => "HUNITS",
+            S57Attribute::HORACC => "HORACC",
+            S57Attribute::HORCLR => "HORCLR",
+            S57Attribute::HORLEN => "HORLEN",
+            S57Attribute::HORWID => "HORWID",
+            S57Attribute::ICEFAC => "ICEFAC",
+            S57Attribute::INFORM => "INFORM",
+            S57Attribute::JRSDTN => "JRSDTN",
+            S57Attribute::JUSTH => "JUSTH",
+            S57Attribute::JUSTV => "JUSTV",
+            S57Attribute::LIFCAP => "LIFCAP",
+            S57Attribute::LITCHR => "LITCHR",
+            S57Attribute::LITVIS => "LITVIS",
+            S57Attribute::MARSYS => "MARSYS",
+            S57Attribute::MLTYLT => "MLTYLT",
+            S57Attribute::NATION => "NATION",
+            S57Attribute::NATCON => "NATCON",
+            S57Attribute::NATSUR => "NATSUR",
+            S57Attribute::NATQUA => "NATQUA",
+            S57Attribute::NMDATE => "NMDATE",
+            S57Attribute::OBJNAM => "OBJNAM",
+            S57Attribute::ORIENT => "ORIENT",
+            S57Attribute::PEREND => "PEREND",
+            S57Attribute::PERSTA => "PERSTA",
+            S57Attribute::PICREP => "PICREP",
+            S57Attribute::PILDST => "PILDST",
+            S57Attribute::PUNITS => "PUNITS",
+            S57Attribute::PRCTRY => "PRCTRY",
+            S57Attribute::PRODCT => "PRODCT",
+            S57Attribute::PUBREF => "PUBREF",
+            S57Attribute::QUASOU => "QUASOU",
+            S57Attribute::RADWAL => "RADWAL",
+            S57Attribute::RADIUS => "RADIUS",
+            S57Attribute::RECDAT => "RECDAT",
+            S57Attribute::RECIND => "RECIND",
+            S57Attribute::RYRMGV => "RYRMGV",
+            S57Attribute::RESTRN => "RESTRN",
+            S57Attribute::SCAMAX => "SCAMAX",
+            S57Attribute::SCAMIN => "SCAMIN",
+            S57Attribute::SCVAL1 => "SCVAL1",
+            S57Attribute::SCVAL2 => "SCVAL2",
+            S57Attribute::SECTR1 => "SECTR1",
+            S57Attribute::SECTR2 => "SECTR2",
+            S57Attribute::SHIPAM => "SHIPAM",
+            S57Attribute::SIGFRQ => "SIGFRQ",
+            S57Attribute::SIGGEN => "SIGGEN",
+            S57Attribute::SIGGRP => "SIGGRP",
+            S57Attribute::SIGPER => "SIGPER",
+            S57Attribute::SIGSEQ => "SIGSEQ",
+            S57Attribute::SOUACC => "SOUACC",
+            S57Attribute::SDISMX => "SDISMX",
+            S57Attribute::SDISMN => "SDISMN",
+            S57Attribute::SORDAT => "SORDAT",
+            S57Attribute::SORIND => "SORIND",
+            S57Attribute::STATUS => "STATUS",
+            S57Attribute::SUREND => "SUREND",
+            S57Attribute::SURSTA => "SURSTA",
+            S57Attribute::SURTYP => "SURTYP",
+            S57Attribute::SCALE => "SCALE",
+            S57Attribute::SCODE => "SCODE",
+            S57Attribute::TECSOU => "TECSOU",
+            S57Attribute::TXSTR => "TXSTR",
+            S57Attribute::TXTDSC => "TXTDSC",
+            S57Attribute::TS_TSP => "TS_TSP",
+            S57Attribute::TS_TSV => "TS_TSV",
+            S57Attribute::T_ACWL => "T_ACWL",
+            S57Attribute::T_HWLW => "T_HWLW",
+            S57Attribute::T_MTOD => "T_MTOD",
+            S57Attribute::T_THDF => "T_THDF",
+            S57Attribute::T_TSVL => "T_TSVL",
+            S57Attribute::T_VAHC => "T_VAHC",
+            S57Attribute::T_TINT => "T_TINT",
+            S57Attribute::TIMEND => "TIMEND",
+            S57Attribute::TIMSTA => "TIMSTA",
+            S57Attribute::TINTS => "TINTS",
+            S57Attribute::TOPSHP => "TOPSHP",
+            S57Attribute::TRAFIC => "TRAFIC",
+            S57Attribute::VALACM => "VALACM",
+            S57Attribute::VALDCO => "VALDCO",
+            S57Attribute::VALLMA => "VALLMA",
+            S57Attribute::VALMAG => "VALMAG",
+            S57Attribute::VALMXR => "VALMXR",
+            S57Attribute::VALNMR => "VALNMR",
+            S57Attribute::VALSOU => "VALSOU",
+            S57Attribute::VERACC => "VERACC",
+            S57Attribute::VERCLR => "VERCLR",
+            S57Attribute::VERCCL => "VERCCL",
+            S57Attribute::VERCOP => "VERCOP",
+            S57Attribute::VERCSA => "VERCSA",
+            S57Attribute::VERDAT => "VERDAT",
+            S57Attribute::VERLEN => "VERLEN",
+            S57Attribute::WATLEV => "WATLEV",
+            S57Attribute::NINFOM => "NINFOM",
+            S57Attribute::NOBJNM => "NOBJNM",
+            S57Attribute::NPLDST => "NPLDST",
+            S57Attribute::NTXST => "NTXST",
+            S57Attribute::NTXTDS => "NTXTDS",
+            S57Attribute::HORDAT => "HORDAT",
+            S57Attribute::POSACC => "POSACC",
+            S57Attribute::QUAPOS => "QUAPOS",
+        }
+    }
+
+    /// Attribute name from the S-57 catalogue, e.g. "Depth range value 1"
+    pub fn description(&self) -> &'static str {
+        match self {
+            S57Attribute::Unknown => "Unknown attribute",
+            S57Attribute::AGENCY => "Agency responsible for production",
+            S57Attribute::BCNSHP => "Beacon shape",
+            S57Attribute::BUISHP => "Building shape",
+            S57Attribute::BOYSHP => "Buoy shape",
+            S57Attribute::BURDEP => "Buried depth",
+            S57Attribute::CALSGN => "Call sign",
+            S57Attribute::CATAIR => "Category of airport/airfield",
+            S57Attribute::CATACH => "Category of anchorage",
+            S57Attribute::CATBRG => "Category of bridge",
+            S57Attribute::CATBUA => "Category of built-up area",
+            S57Attribute::CATCBL => "Category of cable",
+            S57Attribute::CATCAN => "Category of canal",
+            S57Attribute::CATCAM => "Category of cardinal mark",
+            S57Attribute::CATCHP => "Category of checkpoint",
+            S57Attribute::CATCOA => "Category of coastline",
+            S57Attribute::CATCTR => "Category of control point",
+            S57Attribute::CATCON => "Category of conveyor",
+            S57Attribute::CATCOV => "Category of coverage",
+            S57Attribute::CATCRN => "Category of crane",
+            S57Attribute::CATDAM => "Category of dam",
+            S57Attribute::CATDIS => "Category of distance mark",
+            S57Attribute::CATDOC => "Category of dock",
+            S57Attribute::CATDPG => "Category of dumping ground",
+            S57Attribute::CATFNC => "Category of fenceline",
+            S57Attribute::CATFRY => "Category of ferry",
+            S57Attribute::CATFIF => "Category of fishing facility",
+            S57Attribute::CATFOG => "Category of fog signal",
+            S57Attribute::CATFOR => "Category of fortified structure",
+            S57Attribute::CATGAT => "Category of gate",
+            S57Attribute::CATICE => "Category of ice",
+            S57Attribute::CATINB => "Category of installation buoy",
+            S57Attribute::CATLND => "Category of land region",
+            S57Attribute::CATLMK => "Category of landmark",
+            S57Attribute::CATLAM => "Category of lateral mark",
+            S57Attribute::CATLIT => "Category of light",
+            S57Attribute::CATMFA => "Category of marine farm/culture",
+            S57Attribute::CATMPA => "Category of military practice area",
+            S57Attribute::CATMOR => "Category of mooring/warping facility",
+            S57Attribute::CATOBS => "Category of obstruction",
+            S57Attribute::CATOFP => "Category of offshore platform",
+            S57Attribute::CATOLB => "Category of oil barrier",
+            S57Attribute::CATPLE => "Category of pile",
+            S57Attribute::CATPIL => "Category of pilot boarding place",
+            S57Attribute::CATPIP => "Category of pipeline/pipe",
+            S57Attribute::CATPRA => "Category of production area",
+            S57Attribute::CATPYL => "Category of pylon",
+            S57Attribute::CATQUA => "Category of quality of data",
+            S57Attribute::CATRAS => "Category of radar station",
+            S57Attribute::CATRTB => "Category of radar transponder beacon",
+            S57Attribute::CATROS => "Category of radio station",
+            S57Attribute::CATTRK => "Category of recommended track",
+            S57Attribute::CATRSC => "Category of rescue station",
+            S57Attribute::CATREA => "Category of restricted area",
+            S57Attribute::CATROD => "Category of road",
+            S57Attribute::CATRUN => "Category of runway",
+            S57Attribute::CATSEA => "Category of sea area",
+            S57Attribute::CATSLC => "Category of shoreline construction",
+            S57Attribute::CATSIT => "Category of signal station, traffic",
+            S57Attribute::CATSIW => "Category of signal station, warning",
+            S57Attribute::CATSIL => "Category of silo/tank",
+            S57Attribute::CATSLO => "Category of slope",
+            S57Attribute::CATSCF => "Category of small craft facility",
+            S57Attribute::CATSPM => "Category of special purpose mark",
+            S57Attribute::CAT_TS => "Category of Tidal Stream",
+            S57Attribute::CATTSS => "Category of Traffic Separation Scheme",
+            S57Attribute::CATVEG => "Category of vegetation",
+            S57Attribute::CATWAT => "Category of water turbulence",
+            S57Attribute::CATWED => "Category of weed/kelp",
+            S57Attribute::CATWRK => "Category of wreck",
+            S57Attribute::SPACE => "Character spacing",
+            S57Attribute::CHARS => "Character specification",
+            S57Attribute::COLOUR => "Colour",
+            S57Attribute::COLPAT => "Colour pattern",
+            S57Attribute::COMCHA => "Communication channel",
+            S57Attribute::CSIZE => "Compass size",
+            S57Attribute::CPDATE => "Compilation date",
+            S57Attribute::CSCALE => "Compilation scale",
+            S57Attribute::CONDTN => "Condition",
+            S57Attribute::CONRAD => "Conspicuous, radar",
+            S57Attribute::CONVIS => "Conspicuous, visually",
+            S57Attribute::CURVEL => "Current velocity",
+            S57Attribute::DATEND => "Date end",
+            S57Attribute::DATSTA => "Date start",
+            S57Attribute::DRVAL1 => "Depth range value 1",
+            S57Attribute::DRVAL2 => "Depth range value 2",
+            S57Attribute::DUNITS => "Depth units",
+            S57Attribute::ELEVAT => "Elevation",
+            S57Attribute::ESTRNG => "Estimated range of transmission",
+            S57Attribute::EXPSOU => "Exposition of sounding",
+            S57Attribute::FUNCTN => "Function",
+            S57Attribute::HEIGHT => "Height",
+            S57Attribute::HUNITS => "Height/length units",
+            S57Attribute::HORACC => "Horizontal accuracy",
+            S57Attribute::HORCLR => "Horizontal clearance",
+            S57Attribute::HORLEN => "Horizontal length",
+            S57Attribute::HORWID => "Horizontal width",
+            S57Attribute::ICEFAC => "Ice factor",
+            S57Attribute::INFORM => "Information",
+            S57Attribute::JRSDTN => "Jurisdiction",
+            S57Attribute::JUSTH => "Justification - horizontal",
+            S57Attribute::JUSTV => "Justification - vertical",
+            S57Attribute::LIFCAP => "Lifting capacity",
+            S57Attribute::LITCHR => "Light characteristic",
+            S57Attribute::LITVIS => "Light visibility",
+            S57Attribute::MARSYS => "Marks navigational - System of",
+            S57Attribute::MLTYLT => "Multiplicity of lights",
+            S57Attribute::NATION => "Nationality",
+            S57Attribute::NATCON => "Nature of construction",
+            S57Attribute::NATSUR => "Nature of surface",
+            S57Attribute::NATQUA => "Nature of surface - qualifying terms",
+            S57Attribute::NMDATE => "Notice to Mariners date",
+            S57Attribute::OBJNAM => "Object name",
+            S57Attribute::ORIENT => "Orientation",
+            S57Attribute::PEREND => "Periodic date end",
+            S57Attribute::PERSTA => "Periodic date start",
+            S57Attribute::PICREP => "Pictorial representation",
+            S57Attribute::PILDST => "Pilot district",
+            S57Attribute::PUNITS => "Positional accuracy units",
+            S57Attribute::PRCTRY => "Producing country",
+            S57Attribute::PRODCT => "Product",
+            S57Attribute::PUBREF => "Publication reference",
+            S57Attribute::QUASOU => "Quality of sounding measurement",
+            S57Attribute::RADWAL => "Radar wave length",
+            S57Attribute::RADIUS => "Radius",
+            S57Attribute::RECDAT => "Recording date",
+            S57Attribute::RECIND => "Recording indication",
+            S57Attribute::RYRMGV => "Reference year for magnetic variation",
+            S57Attribute::RESTRN => "Restriction",
+            S57Attribute::SCAMAX => "Scale maximum",
+            S57Attribute::SCAMIN => "Scale minimum",
+            S57Attribute::SCVAL1 => "Scale value one",
+            S57Attribute::SCVAL2 => "Scale value two",
+            S57Attribute::SECTR1 => "Sector limit one",
+            S57Attribute::SECTR2 => "Sector limit two",
+            S57Attribute::SHIPAM => "Shift parameters",
+            S57Attribute::SIGFRQ => "Signal frequency",
+            S57Attribute::SIGGEN => "Signal generation",
+            S57Attribute::SIGGRP => "Signal group",
+            S57Attribute::SIGPER => "Signal period",
+            S57Attribute::SIGSEQ => "Signal sequence",
+            S57Attribute::SOUACC => "Sounding accuracy",
+            S57Attribute::SDISMX => "Sounding distance - maximum",
+            S57Attribute::SDISMN => "Sounding distance - minimum",
+            S57Attribute::SORDAT => "Source date",
+            S57Attribute::SORIND => "Source indication",
+            S57Attribute::STATUS => "Status",
+            S57Attribute::SUREND => "Survey date - end",
+            S57Attribute::SURSTA => "Survey date - start",
+            S57Attribute::SURTYP => "Survey type",
+            S57Attribute::SCALE => "Symbol scaling factor",
+            S57Attribute::SCODE => "Symbolization code",
+            S57Attribute::TECSOU => "Technique of sounding measurement",
+            S57Attribute::TXSTR => "Text string",
+            S57Attribute::TXTDSC => "Textual description",
+            S57Attribute::TS_TSP => "Tidal stream - panel values",
+            S57Attribute::TS_TSV => "Tidal stream - time series values",
+            S57Attribute::T_ACWL => "Tide - accuracy of water level",
+            S57Attribute::T_HWLW => "Tide - high and low water values",
+            S57Attribute::T_MTOD => "Tide - method of tidal prediction",
+            S57Attribute::T_THDF => "Tide - time and height differences",
+            S57Attribute::T_TSVL => "Tide - time series values",
+            S57Attribute::T_VAHC => "Tide - value of harmonic constituents",
+            S57Attribute::T_TINT => "Tide - time interval of values",
+            S57Attribute::TIMEND => "Time end",
+            S57Attribute::TIMSTA => "Time start",
+            S57Attribute::TINTS => "Tint",
+            S57Attribute::TOPSHP => "Topmark/daymark shape",
+            S57Attribute::TRAFIC => "Traffic flow",
+            S57Attribute::VALACM => "Value of annual change in magnetic variation",
+            S57Attribute::VALDCO => "Value of depth contour",
+            S57Attribute::VALLMA => "Value of local magnetic anomaly",
+            S57Attribute::VALMAG => "Value of magnetic variation",
+            S57Attribute::VALMXR => "Value of maximum range",
+            S57Attribute::VALNMR => "Value of nominal range",
+            S57Attribute::VALSOU => "Value of sounding",
+            S57Attribute::VERACC => "Vertical accuracy",
+            S57Attribute::VERCLR => "Vertical clearance",
+            S57Attribute::VERCCL => "Vertical clearance, closed",
+            S57Attribute::VERCOP => "Vertical clearance, open",
+            S57Attribute::VERCSA => "Vertical clearance, safe",
+            S57Attribute::VERDAT => "Vertical datum",
+            S57Attribute::VERLEN => "Vertical length",
+            S57Attribute::WATLEV => "Water level effect",
+            S57Attribute::NINFOM => "Information in national language",
+            S57Attribute::NOBJNM => "Object name in national language",
+            S57Attribute::NPLDST => "Pilot district in national language",
+            S57Attribute::NTXST => "Text string in national language",
+            S57Attribute::NTXTDS => "Textual description in national language",
+            S57Attribute::HORDAT => "Horizontal datum",
+            S57Attribute::POSACC => "Positional Accuracy",
+            S57Attribute::QUAPOS => "Quality of position",
+        }
+    }
+
+    /// Attribute for an acronym as listed in the S-57 catalogue, ignoring
+    /// surrounding whitespace. `Unknown` if there is no such attribute.
+    pub fn from_acronym(s: &str) -> S57Attribute {
+        match s.trim() {
+            "AGENCY" => S57Attribute::AGENCY,
+            "BCNSHP" => S57Attribute::BCNSHP,
+            "BUISHP" => S57Attribute::BUISHP,
+            "BOYSHP" => S57Attribute::BOYSHP,
+            "BURDEP" => S57Attribute::BURDEP,
+            "CALSGN" => S57Attribute::CALSGN,
+            "CATAIR" => S57Attribute::CATAIR,
+            "CATACH" => S57Attribute::CATACH,
+            "CATBRG" => S57Attribute::CATBRG,
+            "CATBUA" => S57Attribute::CATBUA,
+            "CATCBL" => S57Attribute::CATCBL,
+            "CATCAN" => S57Attribute::CATCAN,
+            "CATCAM" => S57Attribute::CATCAM,
+            "CATCHP" => S57Attribute::CATCHP,
+            "CATCOA" => S57Attribute::CATCOA,
+            "CATCTR" => S57Attribute::CATCTR,
+            "CATCON" => S57Attribute::CATCON,
+            "CATCOV" => S57Attribute::CATCOV,
+            "CATCRN" => S57Attribute::CATCRN,
+            "CATDAM" => S57Attribute::CATDAM,
+            "CATDIS" => S57Attribute::CATDIS,
+            "CATDOC" => S57Attribute::CATDOC,
+            "CATDPG" => S57Attribute::CATDPG,
+            "CATFNC" => S57Attribute::CATFNC,
+            "CATFRY" => S57Attribute::CATFRY,
+            "CATFIF" => S57Attribute::CATFIF,
+            "CATFOG" => S57Attribute::CATFOG,
+            "CATFOR" => S57Attribute::CATFOR,
+            "CATGAT" => S57Attribute::CATGAT,
+            "CATICE" => S57Attribute::CATICE,
+            "CATINB" => S57Attribute::CATINB,
+            "CATLND" => S57Attribute::CATLND,
+            "CATLMK" => S57Attribute::CATLMK,
+            "CATLAM" => S57Attribute::CATLAM,
+            "CATLIT" => S57Attribute::CATLIT,
+            "CATMFA" => S57Attribute::CATMFA,
+            "CATMPA" => S57Attribute::CATMPA,
+            "CATMOR" => S57Attribute::CATMOR,
+            "CATOBS" => S57Attribute::CATOBS,
+            "CATOFP" => S57Attribute::CATOFP,
+            "CATOLB" => S57Attribute::CATOLB,
+            "CATPLE" => S57Attribute::CATPLE,
+            "CATPIL" => S57Attribute::CATPIL,
+            "CATPIP" => S57Attribute::CATPIP,
+            "CATPRA" => S57Attribute::CATPRA,
+            "CATPYL" => S57Attribute::CATPYL,
+            "CATQUA" => S57Attribute::CATQUA,
+            "CATRAS" => S57Attribute::CATRAS,
+            "CATRTB" => S57Attribute::CATRTB,
+            "CATROS" => S57Attribute::CATROS,
+            "CATTRK" => S57Attribute::CATTRK,
+            "CATRSC" => S57Attribute::CATRSC,
+            "CATREA" => S57Attribute::CATREA,
+            "CATROD" => S57Attribute::CATROD,
+            "CATRUN" => S57Attribute::CATRUN,
+            "CATSEA" => S57Attribute::CATSEA,
+            "CATSLC" => S57Attribute::CATSLC,
+            "CATSIT" => S57Attribute::CATSIT,
+            "CATSIW" => S57Attribute::CATSIW,
+            "CATSIL" => S57Attribute::CATSIL,
+            "CATSLO" => S57Attribute::CATSLO,
+            "CATSCF" => S57Attribute::CATSCF,
+            "CATSPM" => S57Attribute::CATSPM,
+            "CAT_TS" => S57Attribute::CAT_TS,
+            "CATTSS" => S57Attribute::CATTSS,
+            "CATVEG" => S57Attribute::CATVEG,
+            "CATWAT" => S57Attribute::CATWAT,
+            "CATWED" => S57Attribute::CATWED,
+            "CATWRK" => S57Attribute::CATWRK,
+            "SPACE" => S57Attribute::SPACE,
+            "CHARS" => S57Attribute::CHARS,
+            "COLOUR" => S57Attribute::COLOUR,
+            "COLPAT" => S57Attribute::COLPAT,
+            "COMCHA" => S57Attribute::COMCHA,
+            "CSIZE" => S57Attribute::CSIZE,
+            "CPDATE" => S57Attribute::CPDATE,
+            "CSCALE" => S57Attribute::CSCALE,
+            "CONDTN" => S57Attribute::CONDTN,
+            "CONRAD" => S57Attribute::CONRAD,
+            "CONVIS" => S57Attribute::CONVIS,
+            "CURVEL" => S57Attribute::CURVEL,
+            "DATEND" => S57Attribute::DATEND,
+            "DATSTA" => S57Attribute::DATSTA,
+            "DRVAL1" => S57Attribute::DRVAL1,
+            "DRVAL2" => S57Attribute::DRVAL2,
+            "DUNITS" => S57Attribute::DUNITS,
+            "ELEVAT" => S57Attribute::ELEVAT,
+            "ESTRNG" => S57Attribute::ESTRNG,
+            "EXPSOU" => S57Attribute::EXPSOU,
+            "FUNCTN" => S57Attribute::FUNCTN,
+            "HEIGHT" => S57Attribute::HEIGHT,
+            "HUNITS" => S57Attribute::HUNITS,
+            "HORACC" => S57Attribute::HORACC,
+            "HORCLR" => S57Attribute::HORCLR,
+            "HORLEN" => S57Attribute::HORLEN,
+            "HORWID" => S57Attribute::HORWID,
+            "ICEFAC" => S57Attribute::ICEFAC,
+            "INFORM" => S57Attribute::INFORM,
+            "JRSDTN" => S57Attribute::JRSDTN,
+            "JUSTH" => S57Attribute::JUSTH,
+            "JUSTV" => S57Attribute::JUSTV,
+            "LIFCAP" => S57Attribute::LIFCAP,
+            "LITCHR" => S57Attribute::LITCHR,
+            "LITVIS" => S57Attribute::LITVIS,
+            "MARSYS" => S57Attribute::MARSYS,
+            "MLTYLT" => S57Attribute::MLTYLT,
+            "NATION" => S57Attribute::NATION,
+            "NATCON" => S57Attribute::NATCON,
+            "NATSUR" => S57Attribute::NATSUR,
+            "NATQUA" => S57Attribute::NATQUA,
+            "NMDATE" => S57Attribute::NMDATE,
+            "OBJNAM" => S57Attribute::OBJNAM,
+            "ORIENT" => S57Attribute::ORIENT,
+            "PEREND" => S57Attribute::PEREND,
+            "PERSTA" => S57Attribute::PERSTA,
+            "PICREP" => S57Attribute::PICREP,
+            "PILDST" => S57Attribute::PILDST,
+            "PUNITS" => S57Attribute::PUNITS,
+            "PRCTRY" => S57Attribute::PRCTRY,
+            "PRODCT" => S57Attribute::PRODCT,
+            "PUBREF" => S57Attribute::PUBREF,
+            "QUASOU" => S57Attribute::QUASOU,
+            "RADWAL" => S57Attribute::RADWAL,
+            "RADIUS" => S57Attribute::RADIUS,
+            "RECDAT" => S57Attribute::RECDAT,
+            "RECIND" => S57Attribute::RECIND,
+            "RYRMGV" => S57Attribute::RYRMGV,
+            "RESTRN" => S57Attribute::RESTRN,
+            "SCAMAX" => S57Attribute::SCAMAX,
+            "SCAMIN" => S57Attribute::SCAMIN,
+            "SCVAL1" => S57Attribute::SCVAL1,
+            "SCVAL2" => S57Attribute::SCVAL2,
+            "SECTR1" => S57Attribute::SECTR1,
+            "SECTR2" => S57Attribute::SECTR2,
+            "SHIPAM" => S57Attribute::SHIPAM,
+            "SIGFRQ" => S57Attribute::SIGFRQ,
+            "SIGGEN" => S57Attribute::SIGGEN,
+            "SIGGRP" => S57Attribute::SIGGRP,
+            "SIGPER" => S57Attribute::SIGPER,
+            "SIGSEQ" => S57Attribute::SIGSEQ,
+            "SOUACC" => S57Attribute::SOUACC,
+            "SDISMX" => S57Attribute::SDISMX,
+            "SDISMN" => S57Attribute::SDISMN,
+            "SORDAT" => S57Attribute::SORDAT,
+            "SORIND" => S57Attribute::SORIND,
+            "STATUS" => S57Attribute::STATUS,
+            "SUREND" => S57Attribute::SUREND,
+            "SURSTA" => S57Attribute::SURSTA,
+            "SURTYP" => S57Attribute::SURTYP,
+            "SCALE" => S57Attribute::SCALE,
+            "SCODE" => S57Attribute::SCODE,
+            "TECSOU" => S57Attribute::TECSOU,
+            "TXSTR" => S57Attribute::TXSTR,
+            "TXTDSC" => S57Attribute::TXTDSC,
+            "TS_TSP" => S57Attribute::TS_TSP,
+            "TS_TSV" => S57Attribute::TS_TSV,
+            "T_ACWL" => S57Attribute::T_ACWL,
+            "T_HWLW" => S57Attribute::T_HWLW,
+            "T_MTOD" => S57Attribute::T_MTOD,
+            "T_THDF" => S57Attribute::T_THDF,
+            "T_TSVL" => S57Attribute::T_TSVL,
+            "T_VAHC" => S57Attribute::T_VAHC,
+            "T_TINT" => S57Attribute::T_TINT,
+            "TIMEND" => S57Attribute::TIMEND,
+            "TIMSTA" => S57Attribute::TIMSTA,
+            "TINTS" => S57Attribute::TINTS,
+            "TOPSHP" => S57Attribute::TOPSHP,
+            "TRAFIC" => S57Attribute::TRAFIC,
+            "VALACM" => S57Attribute::VALACM,
+            "VALDCO" => S57Attribute::VALDCO,
+            "VALLMA" => S57Attribute::VALLMA,
+            "VALMAG" => S57Attribute::VALMAG,
+            "VALMXR" => S57Attribute::VALMXR,
+            "VALNMR" => S57Attribute::VALNMR,
+            "VALSOU" => S57Attribute::VALSOU,
+            "VERACC" => S57Attribute::VERACC,
+            "VERCLR" => S57Attribute::VERCLR,
+            "VERCCL" => S57Attribute::VERCCL,
+            "VERCOP" => S57Attribute::VERCOP,
+            "VERCSA" => S57Attribute::VERCSA,
+            "VERDAT" => S57Attribute::VERDAT,
+            "VERLEN" => S57Attribute::VERLEN,
+            "WATLEV" => S57Attribute::WATLEV,
+            "NINFOM" => S57Attribute::NINFOM,
+            "NOBJNM" => S57Attribute::NOBJNM,
+            "NPLDST" => S57Attribute::NPLDST,
+            "NTXST" => S57Attribute::NTXST,
+            "NTXTDS" => S57Attribute::NTXTDS,
+            "HORDAT" => S57Attribute::HORDAT,
+            "POSACC" => S57Attribute::POSACC,
+            "QUAPOS" => S57Attribute::QUAPOS,
+            _ => S57Attribute::Unknown,
+        }
+    }
+
+    /// Value type of the attribute in the S-57 catalogue
+    pub fn kind(&self) -> AttributeKind {
+        match self {
+            _ if self.is_float() => AttributeKind::Float,
+            S57Attribute::BCNSHP
+            | S57Attribute::BUISHP
+            | S57Attribute::BOYSHP
+            | S57Attribute::CATBUA
+            | S57Attribute::CATCBL
+            | S57Attribute::CATCAN
+            | S57Attribute::CATCAM
+            | S57Attribute::CATCHP
+            | S57Attribute::CATCOA
+            | S57Attribute::CATCTR
+            | S57Attribute::CATCON
+            | S57Attribute::CATCOV
+            | S57Attribute::CATCRN
+            | S57Attribute::CATDAM
+            | S57Attribute::CATDIS
+            | S57Attribute::CATDOC
+            | S57Attribute::CATFNC
+            | S57Attribute::CATFRY
+            | S57Attribute::CATFIF
+            | S57Attribute::CATFOG
+            | S57Attribute::CATGAT
+            | S57Attribute::CATICE
+            | S57Attribute::CATINB
+            | S57Attribute::CATLAM
+            | S57Attribute::CATMFA
+            | S57Attribute::CATMOR
+            | S57Attribute::CATOBS
+            | S57Attribute::CATOLB
+            | S57Attribute::CATPLE
+            | S57Attribute::CATPRA
+            | S57Attribute::CATPYL
+            | S57Attribute::CATQUA
+            | S57Attribute::CATRAS
+            | S57Attribute::CATRTB
+            | S57Attribute::CATTRK
+            | S57Attribute::CATROD
+            | S57Attribute::CATRUN
+            | S57Attribute::CATSEA
+            | S57Attribute::CATSLC
+            | S57Attribute::CATSIL
+            | S57Attribute::CATSLO
+            | S57Attribute::CAT_TS
+            | S57Attribute::CATTSS
+            | S57Attribute::CATWAT
+            | S57Attribute::CATWED
+            | S57Attribute::CATWRK
+            | S57Attribute::SPACE
+            | S57Attribute::CONDTN
+            | S57Attribute::CONRAD
+            | S57Attribute::CONVIS
+            | S57Attribute::DUNITS
+            | S57Attribute::EXPSOU
+            | S57Attribute::HUNITS
+            | S57Attribute::JRSDTN
+            | S57Attribute::JUSTH
+            | S57Attribute::JUSTV
+            | S57Attribute::LITCHR
+            | S57Attribute::MARSYS
+            | S57Attribute::PUNITS
+            | S57Attribute::PRCTRY
+            | S57Attribute::SIGGEN
+            | S57Attribute::T_ACWL
+            | S57Attribute::T_MTOD
+            | S57Attribute::TINTS
+            | S57Attribute::TOPSHP
+            | S57Attribute::TRAFIC
+            | S57Attribute::VERDAT
+            | S57Attribute::WATLEV
+            | S57Attribute::HORDAT
+            | S57Attribute::QUAPOS => AttributeKind::Enumerated,
+            S57Attribute::CATAIR
+            | S57Attribute::CATACH
+            | S57Attribute::CATBRG
+            | S57Attribute::CATDPG
+            | S57Attribute::CATFOR
+            | S57Attribute::CATLND
+            | S57Attribute::CATLMK
+            | S57Attribute::CATLIT
+            | S57Attribute::CATMPA
+            | S57Attribute::CATOFP
+            | S57Attribute::CATPIL
+            | S57Attribute::CATPIP
+            | S57Attribute::CATROS
+            | S57Attribute::CATRSC
+            | S57Attribute::CATREA
+            | S57Attribute::CATSIT
+            | S57Attribute::CATSIW
+            | S57Attribute::CATSCF
+            | S57Attribute::CATSPM
+            | S57Attribute::CATVEG
+            | S57Attribute::COLOUR
+            | S57Attribute::COLPAT
+            | S57Attribute::FUNCTN
+            | S57Attribute::LITVIS
+            | S57Attribute::NATCON
+            | S57Attribute::NATSUR
+            | S57Attribute::NATQUA
+            | S57Attribute::PRODCT
+            | S57Attribute::QUASOU
+            | S57Attribute::RESTRN
+            | S57Attribute::STATUS
+            | S57Attribute::SURTYP
+            | S57Attribute::TECSOU => AttributeKind::List,
+            S57Attribute::CSIZE
+            | S57Attribute::ICEFAC
+            | S57Attribute::LIFCAP
+            | S57Attribute::SCALE
+            | S57Attribute::TS_TSV => AttributeKind::Float,
+            S57Attribute::CSCALE
+            | S57Attribute::MLTYLT
+            | S57Attribute::SCAMAX
+            | S57Attribute::SCAMIN
+            | S57Attribute::SCVAL1
+            | S57Attribute::SCVAL2
+            | S57Attribute::SIGFRQ
+            | S57Attribute::T_TINT => AttributeKind::Integer,
+            S57Attribute::AGENCY
+            | S57Attribute::CHARS
+            | S57Attribute::COMCHA
+            | S57Attribute::CPDATE
+            | S57Attribute::DATEND
+            | S57Attribute::DATSTA
+            | S57Attribute::NATION
+            | S57Attribute::NMDATE
+            | S57Attribute::PEREND
+            | S57Attribute::PERSTA
+            | S57Attribute::RADWAL
+            | S57Attribute::RECDAT
+            | S57Attribute::RECIND
+            | S57Attribute::RYRMGV
+            | S57Attribute::SHIPAM
+            | S57Attribute::SIGGRP
+            | S57Attribute::SIGSEQ
+            | S57Attribute::SORDAT
+            | S57Attribute::SORIND
+            | S57Attribute::SUREND
+            | S57Attribute::SURSTA
+            | S57Attribute::SCODE
+            | S57Attribute::TS_TSP
+            | S57Attribute::T_HWLW
+            | S57Attribute::T_THDF
+            | S57Attribute::T_TSVL
+            | S57Attribute::T_VAHC
+            | S57Attribute::TIMEND
+            | S57Attribute::TIMSTA => AttributeKind::CodedString,
+            S57Attribute::CALSGN
+            | S57Attribute::INFORM
+            | S57Attribute::OBJNAM
+            | S57Attribute::PICREP
+            | S57Attribute::PILDST
+            | S57Attribute::PUBREF
+            | S57Attribute::TXSTR
+            | S57Attribute::TXTDSC
+            | S57Attribute::NINFOM
+            | S57Attribute::NOBJNM
+            | S57Attribute::NPLDST
+            | S57Attribute::NTXST
+            | S57Attribute::NTXTDS => AttributeKind::FreeText,
+            _ => AttributeKind::Unknown,
+        }
+    }
+
+    /// Attributes the S-57 catalogue defines as floating point values
+    pub fn is_float(&self) -> bool {
+        matches!(
+            self,
+            S57Attribute::BURDEP
+                | S57Attribute::CURVEL
+                | S57Attribute::DRVAL1
+                | S57Attribute::DRVAL2
+                | S57Attribute::ELEVAT
+                | S57Attribute::ESTRNG
+                | S57Attribute::HEIGHT
+                | S57Attribute::HORACC
+                | S57Attribute::HORCLR
+                | S57Attribute::HORLEN
+                | S57Attribute::HORWID
+                | S57Attribute::ORIENT
+                | S57Attribute::POSACC
+                | S57Attribute::RADIUS
+                | S57Attribute::SDISMN
+                | S57Attribute::SDISMX
+                | S57Attribute::SECTR1
+                | S57Attribute::SECTR2
+                | S57Attribute::SIGPER
+                | S57Attribute::SOUACC
+                | S57Attribute::VALACM
+                | S57Attribute::VALDCO
+                | S57Attribute::VALLMA
+                | S57Attribute::VALMAG
+                | S57Attribute::VALMXR
+                | S57Attribute::VALNMR
+                | S57Attribute::VALSOU
+                | S57Attribute::VERACC
+                | S57Attribute::VERCCL
+                | S57Attribute::VERCLR
+                | S57Attribute::VERCOP
+                | S57Attribute::VERCSA
+                | S57Attribute::VERLEN
+        )
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[allow(dead_code, non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
+#[allow(dead_code, non_camel_case_types, clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum S57Type {
     Unknown = 0,
     ADMARE = 1,   // Administration Area (Named)
@@ -1213,4 +2869,733 @@ impl S57Type {
             _ => S57Type::Unknown,
         }
     }
+
+    /// Six letter object class code, e.g. "DEPARE"
+    pub fn acronym(&self) -> &'static str {
+        match self {
+            S57Type::Unknown => "Unknown",
+            S57Type::ADMARE => "ADMARE",
+            S57Type::AIRARE => "AIRARE",
+            S57Type::ACHBRT => "ACHBRT",
+            S57Type::ACHARE => "ACHARE",
+            S57Type::BCNCAR => "BCNCAR",
+            S57Type::BCNISD => "BCNISD",
+            S57Type::BCNLAT => "BCNLAT",
+            S57Type::BCNSAW => "BCNSAW",
+            S57Type::BCNSPP => "BCNSPP",
+            S57Type::BERTHS => "BERTHS",
+            S57Type::BRIDGE => "BRIDGE",
+            S57Type::BUISGL => "BUISGL",
+            S57Type::BUAARE => "BUAARE",
+            S57Type::BOYCAR => "BOYCAR",
+            S57Type::BOYINB => "BOYINB",
+            S57Type::BOYISD => "BOYISD",
+            S57Type::BOYLAT => "BOYLAT",
+            S57Type::BOYSAW => "BOYSAW",
+            S57Type::BOYSPP => "BOYSPP",
+            S57Type::CBLARE => "CBLARE",
+            S57Type::CBLOHD => "CBLOHD",
+            S57Type::CBLSUB => "CBLSUB",
+            S57Type::CANALS => "CANALS",
+            S57Type::CANBNK => "CANBNK",
+            S57Type::CTSARE => "CTSARE",
+            S57Type::CAUSWY => "CAUSWY",
+            S57Type::CTNARE => "CTNARE",
+            S57Type::CHKPNT => "CHKPNT",
+            S57Type::CGUSTA => "CGUSTA",
+            S57Type::COALNE => "COALNE",
+            S57Type::CONZNE => "CONZNE",
+            S57Type::COSARE => "COSARE",
+            S57Type::CTRPNT => "CTRPNT",
+            S57Type::CONVYR => "CONVYR",
+            S57Type::CRANES => "CRANES",
+            S57Type::CURENT => "CURENT",
+            S57Type::CUSZNE => "CUSZNE",
+            S57Type::DAMCON => "DAMCON",
+            S57Type::DAYMAR => "DAYMAR",
+            S57Type::DWRTCL => "DWRTCL",
+            S57Type::DWRTPT => "DWRTPT",
+            S57Type::DEPARE => "DEPARE",
+            S57Type::DEPCNT => "DEPCNT",
+            S57Type::DISMAR => "DISMAR",
+            S57Type::DOCARE => "DOCARE",
+            S57Type::DRGARE => "DRGARE",
+            S57Type::DRYDOC => "DRYDOC",
+            S57Type::DMPGRD => "DMPGRD",
+            S57Type::DYKCON => "DYKCON",
+            S57Type::EXEZNE => "EXEZNE",
+            S57Type::FAIRWY => "FAIRWY",
+            S57Type::FNCLNE => "FNCLNE",
+            S57Type::FERYRT => "FERYRT",
+            S57Type::FSHZNE => "FSHZNE",
+            S57Type::FSHFAC => "FSHFAC",
+            S57Type::FSHGRD => "FSHGRD",
+            S57Type::FLODOC => "FLODOC",
+            S57Type::FOGSIG => "FOGSIG",
+            S57Type::FORSTC => "FORSTC",
+            S57Type::FRPARE => "FRPARE",
+            S57Type::GATCON => "GATCON",
+            S57Type::GRIDRN => "GRIDRN",
+            S57Type::HRBARE => "HRBARE",
+            S57Type::HRBFAC => "HRBFAC",
+            S57Type::HULKES => "HULKES",
+            S57Type::ICEARE => "ICEARE",
+            S57Type::ICNARE => "ICNARE",
+            S57Type::ISTZNE => "ISTZNE",
+            S57Type::LAKARE => "LAKARE",
+            S57Type::LAKSHR => "LAKSHR",
+            S57Type::LNDARE => "LNDARE",
+            S57Type::LNDELV => "LNDELV",
+            S57Type::LNDRGN => "LNDRGN",
+            S57Type::LNDMRK => "LNDMRK",
+            S57Type::LIGHTS => "LIGHTS",
+            S57Type::LITFLT => "LITFLT",
+            S57Type::LITVES => "LITVES",
+            S57Type::LOCMAG => "LOCMAG",
+            S57Type::LOKBSN => "LOKBSN",
+            S57Type::LOGPON => "LOGPON",
+            S57Type::MAGVAR => "MAGVAR",
+            S57Type::MARCUL => "MARCUL",
+            S57Type::MIPARE => "MIPARE",
+            S57Type::MORFAC => "MORFAC",
+            S57Type::NAVLNE => "NAVLNE",
+            S57Type::OBSTRN => "OBSTRN",
+            S57Type::OFSPLF => "OFSPLF",
+            S57Type::OSPARE => "OSPARE",
+            S57Type::OILBAR => "OILBAR",
+            S57Type::PILPNT => "PILPNT",
+            S57Type::PILBOP => "PILBOP",
+            S57Type::PIPARE => "PIPARE",
+            S57Type::PIPOHD => "PIPOHD",
+            S57Type::PIPSOL => "PIPSOL",
+            S57Type::PONTON => "PONTON",
+            S57Type::PRCARE => "PRCARE",
+            S57Type::PRDARE => "PRDARE",
+            S57Type::PYLONS => "PYLONS",
+            S57Type::RADLNE => "RADLNE",
+            S57Type::RADRNG => "RADRNG",
+            S57Type::RADRFL => "RADRFL",
+            S57Type::RADSTA => "RADSTA",
+            S57Type::RTPBCN => "RTPBCN",
+            S57Type::RDOCAL => "RDOCAL",
+            S57Type::RDOSTA => "RDOSTA",
+            S57Type::RAILWY => "RAILWY",
+            S57Type::RAPIDS => "RAPIDS",
+            S57Type::RCRTCL => "RCRTCL",
+            S57Type::RECTRC => "RECTRC",
+            S57Type::RCTLPT => "RCTLPT",
+            S57Type::RSCSTA => "RSCSTA",
+            S57Type::RESARE => "RESARE",
+            S57Type::RETRFL => "RETRFL",
+            S57Type::RIVERS => "RIVERS",
+            S57Type::RIVBNK => "RIVBNK",
+            S57Type::ROADWY => "ROADWY",
+            S57Type::RUNWAY => "RUNWAY",
+            S57Type::SNDWAV => "SNDWAV",
+            S57Type::SEAARE => "SEAARE",
+            S57Type::SPLARE => "SPLARE",
+            S57Type::SBDARE => "SBDARE",
+            S57Type::SLCONS => "SLCONS",
+            S57Type::SISTAT => "SISTAT",
+            S57Type::SISTAW => "SISTAW",
+            S57Type::SILTNK => "SILTNK",
+            S57Type::SLOTOP => "SLOTOP",
+            S57Type::SLOGRD => "SLOGRD",
+            S57Type::SMCFAC => "SMCFAC",
+            S57Type::SOUNDG => "SOUNDG",
+            S57Type::SPRING => "SPRING",
+            S57Type::SQUARE => "SQUARE",
+            S57Type::STSLNE => "STSLNE",
+            S57Type::SUBTLN => "SUBTLN",
+            S57Type::SWPARE => "SWPARE",
+            S57Type::TESARE => "TESARE",
+            S57Type::TS_PRH => "TS_PRH",
+            S57Type::TS_FEB => "TS_FEB",
+            S57Type::TS_PNH => "TS_PNH",
+            S57Type::TS_PAD => "TS_PAD",
+            S57Type::TS_TIS => "TS_TIS",
+            S57Type::T_HMON => "T_HMON",
+            S57Type::T_NHMN => "T_NHMN",
+            S57Type::T_TIMS => "T_TIMS",
+            S57Type::TIDEWY => "TIDEWY",
+            S57Type::TOPMAR => "TOPMAR",
+            S57Type::TSELNE => "TSELNE",
+            S57Type::TSSBND => "TSSBND",
+            S57Type::TSSCRS => "TSSCRS",
+            S57Type::TSSLPT => "TSSLPT",
+            S57Type::TSSRON => "TSSRON",
+            S57Type::TSEZNE => "TSEZNE",
+            S57Type::TUNNEL => "TUNNEL",
+            S57Type::TWRTPT => "TWRTPT",
+            S57Type::UWTROC => "UWTROC",
+            S57Type::UNSARE => "UNSARE",
+            S57Type::VEGATN => "VEGATN",
+            S57Type::WATTUR => "WATTUR",
+            S57Type::WATFAL => "WATFAL",
+            S57Type::WEDKLP => "WEDKLP",
+            S57Type::WRECKS => "WRECKS",
+            S57Type::M_ACCY => "M_ACCY",
+            S57Type::M_CSCL => "M_CSCL",
+            S57Type::M_COVR => "M_COVR",
+            S57Type::M_HDAT => "M_HDAT",
+            S57Type::M_HOPA => "M_HOPA",
+            S57Type::M_NPUB => "M_NPUB",
+            S57Type::M_NSYS => "M_NSYS",
+            S57Type::M_PROD => "M_PROD",
+            S57Type::M_QUAL => "M_QUAL",
+            S57Type::M_SDAT => "M_SDAT",
+            S57Type::M_SREL => "M_SREL",
+            S57Type::M_UNIT => "M_UNIT",
+            S57Type::M_VDAT => "M_VDAT",
+            S57Type::C_AGGR => "C_AGGR",
+            S57Type::C_ASSO => "C_ASSO",
+            S57Type::C_STAC => "C_STAC",
+            S57Type::AREAS => "AREAS",
+            S57Type::LINES => "LINES",
+            S57Type::CSYMB => "CSYMB",
+            S57Type::COMPS => "COMPS",
+            S57Type::TEXTS => "TEXTS",
+        }
+    }
+
+    /// Object class name from the S-57 catalogue, e.g. "Depth area"
+    pub fn description(&self) -> &'static str {
+        match self {
+            S57Type::Unknown => "Unknown object class",
+            S57Type::ADMARE => "Administration Area (Named)",
+            S57Type::AIRARE => "Airport/airfield",
+            S57Type::ACHBRT => "Anchor berth",
+            S57Type::ACHARE => "Anchorage area",
+            S57Type::BCNCAR => "Beacon, cardinal",
+            S57Type::BCNISD => "Beacon, isolated danger",
+            S57Type::BCNLAT => "Beacon, lateral",
+            S57Type::BCNSAW => "Beacon, safe water",
+            S57Type::BCNSPP => "Beacon, special purpose/general",
+            S57Type::BERTHS => "Berth",
+            S57Type::BRIDGE => "Bridge",
+            S57Type::BUISGL => "Building, single",
+            S57Type::BUAARE => "Built-up area",
+            S57Type::BOYCAR => "Buoy, cardinal",
+            S57Type::BOYINB => "Buoy, installation",
+            S57Type::BOYISD => "Buoy, isolated danger",
+            S57Type::BOYLAT => "Buoy, lateral",
+            S57Type::BOYSAW => "Buoy, safe water",
+            S57Type::BOYSPP => "Buoy, special purpose/general",
+            S57Type::CBLARE => "Cable area",
+            S57Type::CBLOHD => "Cable, overhead",
+            S57Type::CBLSUB => "Cable, submarine",
+            S57Type::CANALS => "Canal",
+            S57Type::CANBNK => "Canal bank",
+            S57Type::CTSARE => "Cargo transhipment area",
+            S57Type::CAUSWY => "Causeway",
+            S57Type::CTNARE => "Caution area",
+            S57Type::CHKPNT => "Checkpoint",
+            S57Type::CGUSTA => "Coastguard station",
+            S57Type::COALNE => "Coastline",
+            S57Type::CONZNE => "Contiguous zone",
+            S57Type::COSARE => "Continental shelf area",
+            S57Type::CTRPNT => "Control point",
+            S57Type::CONVYR => "Conveyor",
+            S57Type::CRANES => "Crane",
+            S57Type::CURENT => "Current - non-gravitational1",
+            S57Type::CUSZNE => "Custom zone",
+            S57Type::DAMCON => "Dam",
+            S57Type::DAYMAR => "Daymark",
+            S57Type::DWRTCL => "Deep water route centerline",
+            S57Type::DWRTPT => "Deep water route part",
+            S57Type::DEPARE => "Depth area",
+            S57Type::DEPCNT => "Depth contour",
+            S57Type::DISMAR => "Distance mark",
+            S57Type::DOCARE => "Dock area",
+            S57Type::DRGARE => "Dredged area",
+            S57Type::DRYDOC => "Dry dock",
+            S57Type::DMPGRD => "Dumping ground",
+            S57Type::DYKCON => "Dyke",
+            S57Type::EXEZNE => "Exclusive economic zone",
+            S57Type::FAIRWY => "Fairway",
+            S57Type::FNCLNE => "Fence/wall",
+            S57Type::FERYRT => "Ferry route",
+            S57Type::FSHZNE => "Fishery zone",
+            S57Type::FSHFAC => "Fishing facility",
+            S57Type::FSHGRD => "Fishing ground",
+            S57Type::FLODOC => "Floating dock",
+            S57Type::FOGSIG => "Fog signal",
+            S57Type::FORSTC => "Fortified structure",
+            S57Type::FRPARE => "Free port area",
+            S57Type::GATCON => "Gate",
+            S57Type::GRIDRN => "Gridiron",
+            S57Type::HRBARE => "Harbour area (administrative)",
+            S57Type::HRBFAC => "Harbour facility",
+            S57Type::HULKES => "Hulk",
+            S57Type::ICEARE => "Ice area",
+            S57Type::ICNARE => "Incineration area",
+            S57Type::ISTZNE => "Inshore traffic zone",
+            S57Type::LAKARE => "Lake",
+            S57Type::LAKSHR => "Lake shore",
+            S57Type::LNDARE => "Land area",
+            S57Type::LNDELV => "Land elevation",
+            S57Type::LNDRGN => "Land region",
+            S57Type::LNDMRK => "Landmark",
+            S57Type::LIGHTS => "Light",
+            S57Type::LITFLT => "Light float",
+            S57Type::LITVES => "Light vessel",
+            S57Type::LOCMAG => "Local magnetic anomaly",
+            S57Type::LOKBSN => "Lock basin",
+            S57Type::LOGPON => "Log pond",
+            S57Type::MAGVAR => "Magnetic variation",
+            S57Type::MARCUL => "Marine farm/culture",
+            S57Type::MIPARE => "Military practice area",
+            S57Type::MORFAC => "Mooring/Warping facility",
+            S57Type::NAVLNE => "Navigation line",
+            S57Type::OBSTRN => "Obstruction",
+            S57Type::OFSPLF => "Offshore platform",
+            S57Type::OSPARE => "Offshore production area",
+            S57Type::OILBAR => "Oil barrier",
+            S57Type::PILPNT => "Pile",
+            S57Type::PILBOP => "Pilot boarding place",
+            S57Type::PIPARE => "Pipeline area",
+            S57Type::PIPOHD => "Pipeline, overhead",
+            S57Type::PIPSOL => "Pipeline, submarine/on land",
+            S57Type::PONTON => "Pontoon",
+            S57Type::PRCARE => "Precautionary area",
+            S57Type::PRDARE => "Production/storage area",
+            S57Type::PYLONS => "Pylon/bridge support",
+            S57Type::RADLNE => "Radar line",
+            S57Type::RADRNG => "Radar range",
+            S57Type::RADRFL => "Radar reflector",
+            S57Type::RADSTA => "Radar station",
+            S57Type::RTPBCN => "Radar transponder beacon",
+            S57Type::RDOCAL => "Radio calling-in point",
+            S57Type::RDOSTA => "Radio station",
+            S57Type::RAILWY => "Railway",
+            S57Type::RAPIDS => "Rapids",
+            S57Type::RCRTCL => "Recommended route centerline",
+            S57Type::RECTRC => "Recommended track",
+            S57Type::RCTLPT => "Recommended traffic lane part",
+            S57Type::RSCSTA => "Rescue station",
+            S57Type::RESARE => "Restricted area",
+            S57Type::RETRFL => "Retro-reflector",
+            S57Type::RIVERS => "River",
+            S57Type::RIVBNK => "River bank",
+            S57Type::ROADWY => "Road",
+            S57Type::RUNWAY => "Runway",
+            S57Type::SNDWAV => "Sand waves",
+            S57Type::SEAARE => "Sea area/named water area",
+            S57Type::SPLARE => "Sea-plane landing area",
+            S57Type::SBDARE => "Seabed area",
+            S57Type::SLCONS => "Shoreline construction",
+            S57Type::SISTAT => "Signal station, traffic",
+            S57Type::SISTAW => "Signal station, warning",
+            S57Type::SILTNK => "Silo/tank",
+            S57Type::SLOTOP => "Slope topline",
+            S57Type::SLOGRD => "Sloping ground",
+            S57Type::SMCFAC => "Small craft facility",
+            S57Type::SOUNDG => "Sounding",
+            S57Type::SPRING => "Spring",
+            S57Type::SQUARE => "Square",
+            S57Type::STSLNE => "Straight territorial sea baseline",
+            S57Type::SUBTLN => "Submarine transit lane",
+            S57Type::SWPARE => "Swept Area",
+            S57Type::TESARE => "Territorial sea area",
+            S57Type::TS_PRH => "Tidal stream - harmonic prediction",
+            S57Type::TS_FEB => "Tidal stream - flood / ebb",
+            S57Type::TS_PNH => "Tidal stream - non-harmonic prediction",
+            S57Type::TS_PAD => "Tidal stream panel data",
+            S57Type::TS_TIS => "Tidal stream - time series",
+            S57Type::T_HMON => "Tide - harmonic prediction",
+            S57Type::T_NHMN => "Tide - non-harmonic prediction",
+            S57Type::T_TIMS => "Tide - time series",
+            S57Type::TIDEWY => "Tideway",
+            S57Type::TOPMAR => "Topmark",
+            S57Type::TSELNE => "Traffic separation line",
+            S57Type::TSSBND => "Traffic separation scheme boundary",
+            S57Type::TSSCRS => "Traffic separation scheme crossing",
+            S57Type::TSSLPT => "Traffic separation scheme lane part",
+            S57Type::TSSRON => "Traffic separation scheme roundabout",
+            S57Type::TSEZNE => "Traffic separation zone",
+            S57Type::TUNNEL => "Tunnel",
+            S57Type::TWRTPT => "Two-way route part",
+            S57Type::UWTROC => "Underwater/awash rock",
+            S57Type::UNSARE => "Unsurveyed area",
+            S57Type::VEGATN => "Vegetation",
+            S57Type::WATTUR => "Water turbulence",
+            S57Type::WATFAL => "Waterfall",
+            S57Type::WEDKLP => "Weed/Kelp",
+            S57Type::WRECKS => "Wreck",
+            S57Type::M_ACCY => "Accuracy of data",
+            S57Type::M_CSCL => "Compilation scale of data",
+            S57Type::M_COVR => "Coverage",
+            S57Type::M_HDAT => "Horizontal datum of data",
+            S57Type::M_HOPA => "Horizontal datum shift parameters",
+            S57Type::M_NPUB => "Nautical publication information",
+            S57Type::M_NSYS => "Navigational system of marks",
+            S57Type::M_PROD => "Production information",
+            S57Type::M_QUAL => "Quality of data",
+            S57Type::M_SDAT => "Sounding datum",
+            S57Type::M_SREL => "Survey reliability",
+            S57Type::M_UNIT => "Units of measurement of data",
+            S57Type::M_VDAT => "Vertical datum of data",
+            S57Type::C_AGGR => "Aggregation",
+            S57Type::C_ASSO => "Association",
+            S57Type::C_STAC => "Stacked on/stacked under",
+            S57Type::AREAS => "Cartographic area",
+            S57Type::LINES => "Cartographic line",
+            S57Type::CSYMB => "Cartographic symbol",
+            S57Type::COMPS => "Compass",
+            S57Type::TEXTS => "Text",
+        }
+    }
+
+    /// Numeric object class code, the inverse of `from_type_code`
+    pub fn type_code(&self) -> i32 {
+        *self as i32
+    }
+}
+
+/// Where to put a label relative to its anchor
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum LabelPlacement {
+    /// Offset above and to the right, clear of the point symbol
+    AboveRight,
+    /// Along the line, rotated by `angle` degrees counter-clockwise from east
+    AlongLine { angle: f64 },
+    /// Centered on the anchor, inside the area
+    Centered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(dead_code)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GeometryPrimitive {
+    Unknown = 0,
+    Point = 1,
+    Line = 2,
+    Area = 3,
+    /// Soundings and other point clusters
+    Multipoint = 4,
+}
+
+#[allow(dead_code)]
+impl GeometryPrimitive {
+    /// Whether geometry of primitive `found` is valid for a feature declared
+    /// as `self`. Some encoders declare soundings as plain points.
+    pub fn accepts(self, found: GeometryPrimitive) -> bool {
+        self == found
+            || (self == GeometryPrimitive::Point && found == GeometryPrimitive::Multipoint)
+    }
+
+    pub fn from_u8(primitive: u8) -> Self {
+        match primitive {
+            1 => GeometryPrimitive::Point,
+            2 => GeometryPrimitive::Line,
+            3 => GeometryPrimitive::Area,
+            4 => GeometryPrimitive::Multipoint,
+            _ => GeometryPrimitive::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(lat: f64, lon: f64) -> Position {
+        Position { lat, lon }
+    }
+
+    #[test]
+    fn flat_coords_match_positions() {
+        let mut s57 = S57::new(S57Type::SOUNDG);
+        s57.set_multi_point_geometry(vec![
+            PointGeometry {
+                position: position(54.1, 10.1),
+                value: 3.5,
+            },
+            PointGeometry {
+                position: position(54.2, 10.2),
+                value: 7.0,
+            },
+        ]);
+        s57.set_lines(vec![vec![position(54.3, 10.3), position(54.4, 10.4)]]);
+
+        let flat = s57.flat_coords();
+
+        assert_eq!(
+            flat.coords,
+            vec![10.1, 54.1, 10.2, 54.2, 10.3, 54.3, 10.4, 54.4]
+        );
+        assert_eq!(flat.offsets, vec![0, 4]);
+
+        let mut point = S57::new(S57Type::LNDMRK);
+        point.set_point_geometry(position(54.5, 10.5));
+        assert_eq!(point.flat_coords().coords, vec![10.5, 54.5]);
+    }
+
+    #[test]
+    fn vertex_count_sums_all_geometries() {
+        let mut s57 = S57::new(S57Type::LNDMRK);
+        s57.set_point_geometry(position(54.5, 10.5));
+        s57.set_multi_point_geometry(vec![
+            PointGeometry {
+                position: position(54.1, 10.1),
+                value: 2.0,
+            },
+            PointGeometry {
+                position: position(54.2, 10.2),
+                value: 3.0,
+            },
+        ]);
+        s57.set_lines(vec![
+            vec![
+                position(54.0, 10.0),
+                position(54.0, 10.1),
+                position(54.1, 10.1),
+            ],
+            vec![position(54.3, 10.0), position(54.3, 10.1)],
+        ]);
+        s57.set_polygons(vec![square(54.0, 10.0, 0.1)]);
+
+        assert_eq!(s57.vertex_count(), 1 + 2 + 5 + 5);
+    }
+
+    #[test]
+    fn clipping_keeps_points_on_the_boundary() {
+        let bounds = Rect {
+            top_left: position(55.0, 10.0),
+            bottom_right: position(54.0, 11.0),
+        };
+        let sounding = |lat, lon| PointGeometry {
+            position: position(lat, lon),
+            value: 5.0,
+        };
+
+        let mut s57 = S57::new(S57Type::SOUNDG);
+        s57.set_point_geometry(position(55.0, 10.5));
+        s57.set_multi_point_geometry(vec![sounding(54.0, 11.0), sounding(53.9, 10.5)]);
+
+        let clipped = s57.clipped(&bounds);
+        assert_eq!(clipped.point_geometry(), Some(&position(55.0, 10.5)));
+        assert_eq!(clipped.multi_point_geometry(), &vec![sounding(54.0, 11.0)]);
+    }
+
+    #[test]
+    fn geometry_stale_flag_transitions() {
+        let mut edges = HashMap::new();
+        let mut edge = VectorEdge::new();
+        edge.set_positions(vec![position(54.0, 10.05)]);
+        edges.insert(1, edge);
+        let nodes = HashMap::from([
+            (1, ConnectedNode::new(position(54.0, 10.0))),
+            (2, ConnectedNode::new(position(54.0, 10.1))),
+        ]);
+        let element = LineElement {
+            start_connected_node: 1,
+            edge_vector: 1,
+            end_connected_node: 2,
+            direction: Direction::Forward,
+        };
+
+        let mut s57 = S57::new(S57Type::COALNE);
+        assert!(!s57.is_geometry_stale());
+
+        s57.set_line_geometry(std::slice::from_ref(&element));
+        assert!(s57.is_geometry_stale());
+
+        assert!(s57.build_geometry(&edges, &nodes).is_empty());
+        assert!(!s57.is_geometry_stale());
+        assert_eq!(s57.lines()[0].len(), 3);
+
+        s57.invalidate_geometry();
+        assert!(s57.is_geometry_stale());
+        s57.build_geometry(&edges, &nodes);
+
+        // reading a stale cache returns the previous build
+        s57.set_polygon_geometry(&[element]);
+        assert!(s57.is_geometry_stale());
+        assert_eq!(s57.lines()[0].len(), 3);
+        assert!(s57.polygons().is_empty());
+    }
+
+    fn square(lat: f64, lon: f64, size: f64) -> MultiGeometry {
+        vec![
+            position(lat, lon),
+            position(lat, lon + size),
+            position(lat + size, lon + size),
+            position(lat + size, lon),
+            position(lat, lon),
+        ]
+    }
+
+    #[test]
+    fn area_sums_separate_polygons() {
+        let island = square(54.0, 10.0, 0.1);
+        let hole = square(54.02, 10.02, 0.02);
+        let islet = square(54.5, 10.5, 0.1);
+
+        let mut s57 = S57::new(S57Type::LNDARE);
+        s57.set_polygons(vec![island.clone(), hole.clone(), islet.clone()]);
+
+        let expected = island.area() - hole.area() + islet.area();
+        assert!((s57.area() - expected).abs() < 1.0);
+        assert!(s57.area() > island.area());
+    }
+
+    fn assert_close(actual: Position, expected: Position) {
+        assert!(
+            (actual.lat - expected.lat).abs() < 1e-9 && (actual.lon - expected.lon).abs() < 1e-9,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn great_circle_along_the_equator() {
+        let start = position(0.0, 0.0);
+        let end = position(0.0, 90.0);
+        let quarter = WGS84_SEMIMAJOR_AXIS_METERS * PI / 2.0;
+
+        assert!((start.distance_to(&end) - quarter).abs() < 1e-6);
+        assert_close(start.intermediate(&end, 0.5), position(0.0, 45.0));
+        assert_close(start.destination(90.0, quarter), end);
+        assert_close(start.destination(0.0, quarter / 2.0), position(45.0, 0.0));
+
+        assert!(start.great_circle_points(&end, 0).is_empty());
+        assert_eq!(start.great_circle_points(&end, 1), vec![start]);
+
+        let points = start.great_circle_points(&end, 4);
+        assert_eq!(points.len(), 4);
+        assert_close(points[1], position(0.0, 30.0));
+        assert_close(points[3], end);
+
+        assert_eq!(start.great_circle_points(&start, 3), vec![start; 3]);
+    }
+
+    #[test]
+    fn antipodal_path_is_undefined() {
+        for (start, end) in [
+            (position(0.0, 0.0), position(0.0, 180.0)),
+            (position(54.5, 10.5), position(-54.5, -169.5)),
+        ] {
+            let middle = start.intermediate(&end, 0.5);
+            assert!(middle.lat.is_nan() && middle.lon.is_nan());
+            assert!(start
+                .great_circle_points(&end, 3)
+                .iter()
+                .all(|p| p.lat.is_nan()));
+        }
+
+        // a kilometre short of the antipode is still well defined
+        let near = position(0.0, 0.0).intermediate(&position(0.0, 179.99), 0.5);
+        assert_close(near, position(0.0, 89.995));
+    }
+
+    #[test]
+    fn arc_vertices_lie_on_the_radius() {
+        let center = position(54.5, 10.5);
+
+        let arc = center.arc(1000.0, 0.0, 90.0, 30.0);
+        assert_eq!(arc.len(), 4);
+        assert_close(arc[0], center.destination(0.0, 1000.0));
+        assert_close(arc[3], center.destination(90.0, 1000.0));
+        assert!(arc
+            .iter()
+            .all(|p| (center.distance_to(p) - 1000.0).abs() < 1e-6));
+
+        // equal start and end bearing sweeps the full circle
+        let circle = center.arc(1000.0, 45.0, 45.0, 90.0);
+        assert_eq!(circle.len(), 5);
+        assert_close(circle[0], circle[4]);
+    }
+
+    #[test]
+    fn label_anchors_for_point_line_and_area() {
+        let mut point = S57::new(S57Type::LNDMRK);
+        point.set_point_geometry(position(54.5, 10.5));
+        assert_eq!(
+            point.label_anchor(),
+            Some((position(54.5, 10.5), LabelPlacement::AboveRight))
+        );
+
+        // drawn westward, half of the 3° is on the first segment
+        let mut line = S57::new(S57Type::NAVLNE);
+        line.set_lines(vec![vec![
+            position(0.0, 3.0),
+            position(0.0, 1.0),
+            position(0.0, 0.0),
+        ]]);
+        let (anchor, placement) = line.label_anchor().unwrap();
+        assert_close(anchor, position(0.0, 1.5));
+        assert_eq!(placement, LabelPlacement::AlongLine { angle: 0.0 });
+
+        let mut area = S57::new(S57Type::LNDARE);
+        area.set_polygons(vec![vec![
+            position(0.0, 0.0),
+            position(0.0, 2.0),
+            position(2.0, 2.0),
+            position(2.0, 0.0),
+        ]]);
+        assert_eq!(
+            area.label_anchor(),
+            Some((position(1.0, 1.0), LabelPlacement::Centered))
+        );
+
+        assert_eq!(S57::new(S57Type::M_COVR).label_anchor(), None);
+    }
+
+    #[test]
+    fn nearest_point_on_line_and_vertex() {
+        let mut line = S57::new(S57Type::NAVLNE);
+        line.set_lines(vec![vec![position(0.0, 0.0), position(0.0, 1.0)]]);
+
+        // perpendicular foot on the segment
+        let (nearest, distance) = line.nearest_geometry_point(&position(0.1, 0.5)).unwrap();
+        assert_close(nearest, position(0.0, 0.5));
+        assert!((distance - position(0.1, 0.5).distance_to(&position(0.0, 0.5))).abs() < 1e-6);
+
+        // beyond the end, the end vertex is nearest
+        let (nearest, _) = line.nearest_geometry_point(&position(0.0, 2.0)).unwrap();
+        assert_close(nearest, position(0.0, 1.0));
+
+        let mut point = S57::new(S57Type::LNDMRK);
+        point.set_point_geometry(position(54.5, 10.5));
+        let (nearest, distance) = point.nearest_geometry_point(&position(54.5, 10.5)).unwrap();
+        assert_eq!((nearest, distance), (position(54.5, 10.5), 0.0));
+
+        assert_eq!(
+            S57::new(S57Type::M_COVR).nearest_geometry_point(&position(0.0, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn object_class_acronyms_and_descriptions() {
+        assert_eq!(S57Type::BOYLAT.acronym(), "BOYLAT");
+        assert_eq!(S57Type::BOYLAT.description(), "Buoy, lateral");
+        assert_eq!(S57Type::Unknown.description(), "Unknown object class");
+
+        for code in 1..=u16::MAX {
+            let s57_type = S57Type::from_type_code(code);
+            if s57_type != S57Type::Unknown {
+                assert_eq!(s57_type.acronym(), format!("{:?}", s57_type));
+                assert!(!s57_type.description().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn attribute_acronyms_round_trip() {
+        assert_eq!(S57Attribute::from_acronym(" COLOUR "), S57Attribute::COLOUR);
+        assert_eq!(S57Attribute::from_acronym("colour"), S57Attribute::Unknown);
+        assert_eq!(S57Attribute::from_acronym("XXXXXX"), S57Attribute::Unknown);
+
+        for code in 1..=u16::MAX {
+            let attribute = S57Attribute::from_type_code(code);
+            if attribute != S57Attribute::Unknown {
+                assert_eq!(attribute.acronym(), format!("{:?}", attribute));
+                assert_eq!(S57Attribute::from_acronym(attribute.acronym()), attribute);
+            }
+        }
+    }
 }
@@ -0,0 +1,147 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use std::collections::HashMap;
+
+use crate::s57::{PointGeometry, Position};
+
+/// Grid cell size in degrees, roughly 1 km at the equator
+const CELL_SIZE: f64 = 0.01;
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// Uniform grid over soundings for depth queries around a position
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct SoundingIndex {
+    cells: HashMap<(i32, i32), Vec<PointGeometry>>,
+}
+
+fn cell_of(position: &Position) -> (i32, i32) {
+    (
+        (position.lat / CELL_SIZE).floor() as i32,
+        (position.lon / CELL_SIZE).floor() as i32,
+    )
+}
+
+#[allow(dead_code)]
+impl SoundingIndex {
+    pub fn new<'a, I: IntoIterator<Item = &'a PointGeometry>>(soundings: I) -> Self {
+        let mut cells: HashMap<(i32, i32), Vec<PointGeometry>> = HashMap::new();
+
+        for sounding in soundings {
+            cells
+                .entry(cell_of(&sounding.position))
+                .or_default()
+                .push(sounding.clone());
+        }
+
+        Self { cells }
+    }
+
+    pub fn insert(&mut self, sounding: PointGeometry) {
+        self.cells
+            .entry(cell_of(&sounding.position))
+            .or_default()
+            .push(sounding);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.values().map(|cell| cell.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Shallowest sounding within `radius_m` metres of `pos`
+    pub fn query_min_depth(&self, pos: &Position, radius_m: f64) -> Option<f64> {
+        let d_lat = radius_m / METERS_PER_DEGREE;
+        let d_lon = radius_m / (METERS_PER_DEGREE * pos.lat.to_radians().cos().max(1e-6));
+
+        let (min_lat, min_lon) = cell_of(&Position {
+            lat: pos.lat - d_lat,
+            lon: pos.lon - d_lon,
+        });
+        let (max_lat, max_lon) = cell_of(&Position {
+            lat: pos.lat + d_lat,
+            lon: pos.lon + d_lon,
+        });
+
+        let mut min_depth: Option<f64> = None;
+
+        for lat in min_lat..=max_lat {
+            for lon in min_lon..=max_lon {
+                let Some(cell) = self.cells.get(&(lat, lon)) else {
+                    continue;
+                };
+
+                for sounding in cell {
+                    if sounding.position.distance_to(pos) > radius_m {
+                        continue;
+                    }
+
+                    if min_depth.is_none_or(|depth| sounding.value < depth) {
+                        min_depth = Some(sounding.value);
+                    }
+                }
+            }
+        }
+
+        min_depth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sounding(lat: f64, lon: f64, value: f64) -> PointGeometry {
+        PointGeometry {
+            position: Position { lat, lon },
+            value,
+        }
+    }
+
+    #[test]
+    fn shallowest_sounding_within_radius() {
+        let soundings = [
+            // ~110 m north, in the neighbouring grid cell
+            sounding(54.501, 10.5, 7.5),
+            // ~330 m north
+            sounding(54.503, 10.5, 4.0),
+            // ~6.5 km east
+            sounding(54.5, 10.6, 1.2),
+            sounding(54.5, 10.5005, 9.0),
+        ];
+        let index = SoundingIndex::new(&soundings);
+        let vessel = Position {
+            lat: 54.5,
+            lon: 10.5,
+        };
+
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.query_min_depth(&vessel, 50.0), Some(9.0));
+        assert_eq!(index.query_min_depth(&vessel, 200.0), Some(7.5));
+        assert_eq!(index.query_min_depth(&vessel, 500.0), Some(4.0));
+        assert_eq!(index.query_min_depth(&vessel, 10_000.0), Some(1.2));
+        assert_eq!(
+            index.query_min_depth(&Position { lat: 0.0, lon: 0.0 }, 500.0),
+            None
+        );
+    }
+}
@@ -0,0 +1,204 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use std::fmt::Write;
+
+use crate::{
+    chartfile::ChartFile,
+    s57::{Position, Rect, S57Type, DEGREE, S57},
+};
+
+const LAND: &str = "#e3cf9f";
+const WATER: &str = "#b5d7f0";
+const OUTLINE: &str = "#808080";
+const LINE: &str = "#000000";
+
+/// Maps positions into a `width` x `height` pixel box covering the extent,
+/// mercator projected
+struct Projection {
+    west: f64,
+    east: f64,
+    north_y: f64,
+    south_y: f64,
+    width: f64,
+    height: f64,
+}
+
+fn mercator_y(lat: f64) -> f64 {
+    (std::f64::consts::FRAC_PI_4 + lat * DEGREE / 2.0)
+        .tan()
+        .ln()
+}
+
+impl Projection {
+    fn new(extent: &Rect, width: u32, height: u32) -> Self {
+        Self {
+            west: extent.top_left.lon,
+            east: extent.bottom_right.lon,
+            north_y: mercator_y(extent.top_left.lat),
+            south_y: mercator_y(extent.bottom_right.lat),
+            width: width as f64,
+            height: height as f64,
+        }
+    }
+
+    fn project(&self, position: &Position) -> (f64, f64) {
+        let lon_span = self.east - self.west;
+        let y_span = self.north_y - self.south_y;
+
+        let x = if lon_span != 0.0 {
+            (position.lon - self.west) / lon_span * self.width
+        } else {
+            0.0
+        };
+        let y = if y_span != 0.0 {
+            (self.north_y - mercator_y(position.lat)) / y_span * self.height
+        } else {
+            0.0
+        };
+
+        (x, y)
+    }
+
+    /// SVG path data of a polyline, closed with `Z` for rings
+    fn path<'a, I: IntoIterator<Item = &'a Position>>(&self, positions: I, closed: bool) -> String {
+        let mut data = String::new();
+
+        for (i, position) in positions.into_iter().enumerate() {
+            let (x, y) = self.project(position);
+            let command = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(data, "{}{:.1} {:.1} ", command, x, y);
+        }
+
+        if closed && !data.is_empty() {
+            data.push('Z');
+        }
+
+        data.trim_end().to_string()
+    }
+}
+
+fn area_fill(s57: &S57) -> &'static str {
+    match s57.s57_type() {
+        S57Type::LNDARE => LAND,
+        S57Type::DEPARE | S57Type::DRGARE => WATER,
+        _ => "none",
+    }
+}
+
+#[allow(dead_code)]
+impl ChartFile {
+    /// Simple SVG preview of the chart in a `width` x `height` pixel box.
+    /// Land is tan, depth areas blue, lines and points black.
+    pub fn to_svg(&self, width: u32, height: u32) -> String {
+        let projection = Projection::new(self.extent(), width, height);
+        let mut svg = String::new();
+
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        );
+
+        for (_, layer) in self.features_by_priority() {
+            for s57 in layer {
+                if !s57.polygons().is_empty() {
+                    let data = s57
+                        .polygons()
+                        .iter()
+                        .map(|ring| projection.path(ring, true))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let fill = area_fill(s57);
+                    let stroke = if fill == "none" { OUTLINE } else { fill };
+
+                    let _ = write!(
+                        svg,
+                        r#"<path d="{}" fill="{}" fill-rule="evenodd" stroke="{}" stroke-width="0.5"/>"#,
+                        data, fill, stroke
+                    );
+                }
+
+                for line in s57.lines() {
+                    let _ = write!(
+                        svg,
+                        r#"<path d="{}" fill="none" stroke="{}" stroke-width="0.5"/>"#,
+                        projection.path(line, false),
+                        LINE
+                    );
+                }
+
+                let points = s57.point_geometry().into_iter().chain(
+                    s57.multi_point_geometry()
+                        .iter()
+                        .map(|point| &point.position),
+                );
+
+                for position in points {
+                    let (x, y) = projection.project(position);
+                    let _ = write!(
+                        svg,
+                        r#"<circle cx="{:.1}" cy="{:.1}" r="1" fill="{}"/>"#,
+                        x, y, LINE
+                    );
+                }
+            }
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{LAND, LINE};
+    use crate::{chartfile::ChartFile, s57::S57Type, test_support::ChartBuilder};
+
+    #[test]
+    fn one_element_per_feature_in_the_view_box() {
+        let bytes = ChartBuilder::new("SVG")
+            .feature(S57Type::COALNE, 1, 2)
+            .line(&[[1, 1, 2, 1]])
+            .feature(S57Type::LNDARE, 2, 3)
+            .area(&[[1, 1, 2, 1], [2, 2, 1, 1]])
+            .feature(S57Type::LNDMRK, 3, 1)
+            .point(54.0, 11.0)
+            .edges(&[(1, &[(50.0, 0.0)]), (2, &[(50.0, 100.0)])])
+            .nodes(&[(1, 0.0, 0.0), (2, 100.0, 0.0)])
+            .build();
+        let chart = ChartFile::parse_file(&mut Cursor::new(bytes)).unwrap();
+
+        let svg = chart.to_svg(200, 300);
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="300" viewBox="0 0 200 300">"#
+        ));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<path ").count(), 2);
+        assert_eq!(svg.matches(&format!(r#"fill="{}""#, LAND)).count(), 1);
+        // south-east corner of the extent
+        assert!(svg.contains(&format!(
+            r#"<circle cx="200.0" cy="300.0" r="1" fill="{}"/>"#,
+            LINE
+        )));
+    }
+}
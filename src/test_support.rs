@@ -0,0 +1,246 @@
+/*
+ * Copyright © 2024 Silas Pachali
+ *
+ * Licensed under the EUPL, Version 1.2 or – as soon they will be
+ * approved by the European Commission - subsequent versions of the
+ * EUPL (the "Licence");
+ * You may not use this work except in compliance with the Licence.
+ * You may obtain a copy of the Licence at:
+ *
+ * https://joinup.ec.europa.eu/software/page/eupl
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the Licence is distributed on an
+ * "AS IS" basis, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND,
+ * either express or implied. See the Licence for the specific
+ * language governing permissions and limitations under the Licence.
+ */
+
+use crate::s57::S57Type;
+
+const RECORD_BASE_SIZE: usize = 6;
+
+/// Corners of the fixture cell as (lat, lon): sw, nw, ne, se
+const EXTENT: [(f64, f64); 4] = [(54.0, 10.0), (55.0, 10.0), (55.0, 11.0), (54.0, 11.0)];
+
+/// Writes a decrypted SENC stream record by record, little-endian like the
+/// encoder. Projected coordinates of edges and nodes are metres relative to
+/// the cell center at 54.5°N 10.5°E.
+#[allow(dead_code)]
+pub struct ChartBuilder {
+    bytes: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl ChartBuilder {
+    /// Version, cell name and an extent from 54°N 10°E to 55°N 11°E
+    pub fn new(name: &str) -> Self {
+        let extent: Vec<u8> = EXTENT
+            .iter()
+            .flat_map(|(lat, lon)| [*lat, *lon])
+            .flat_map(f64::to_le_bytes)
+            .collect();
+
+        ChartBuilder { bytes: Vec::new() }
+            // HEADER_SENC_VERSION
+            .record(1, &201u16.to_le_bytes())
+            // HEADER_CELL_NAME
+            .record(2, name.as_bytes())
+            // CELL_EXTENT_RECORD
+            .record(100, &extent)
+    }
+
+    pub fn record(mut self, record_type: u16, payload: &[u8]) -> Self {
+        self.bytes.extend_from_slice(&record_type.to_le_bytes());
+        self.bytes
+            .extend_from_slice(&((RECORD_BASE_SIZE + payload.len()) as u32).to_le_bytes());
+        self.bytes.extend_from_slice(payload);
+        self
+    }
+
+    /// HEADER_CELL_EDITION and HEADER_CELL_UPDATE
+    pub fn version(self, edition: u16, update: u16) -> Self {
+        self.record(4, &edition.to_le_bytes())
+            .record(6, &update.to_le_bytes())
+    }
+
+    /// SERVER_STATUS_RECORD as server, decrypt and expire status, expire
+    /// days remaining, grace days allowed and grace days remaining
+    pub fn server_status(self, fields: [u16; 6]) -> Self {
+        let payload: Vec<u8> = fields.into_iter().flat_map(u16::to_le_bytes).collect();
+        self.record(200, &payload)
+    }
+
+    /// FEATURE_ID_RECORD, following records belong to this feature
+    pub fn feature(self, s57_type: S57Type, feature_id: u16, primitive: u8) -> Self {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(s57_type as u16).to_le_bytes());
+        payload.extend_from_slice(&feature_id.to_le_bytes());
+        payload.push(primitive);
+        self.record(64, &payload)
+    }
+
+    /// FEATURE_ATTRIBUTE_RECORD with an integer value
+    pub fn int_attribute(self, type_code: u16, value: u32) -> Self {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&type_code.to_le_bytes());
+        payload.push(0);
+        payload.extend_from_slice(&value.to_le_bytes());
+        self.record(65, &payload)
+    }
+
+    /// FEATURE_ATTRIBUTE_RECORD with a NUL terminated string value
+    pub fn string_attribute(self, type_code: u16, value: &str) -> Self {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&type_code.to_le_bytes());
+        payload.push(4);
+        payload.extend_from_slice(value.as_bytes());
+        payload.push(0);
+        self.record(65, &payload)
+    }
+
+    /// FEATURE_GEOMETRY_RECORD_POINT
+    pub fn point(self, lat: f64, lon: f64) -> Self {
+        let payload: Vec<u8> = [lat, lon].into_iter().flat_map(f64::to_le_bytes).collect();
+        self.record(80, &payload)
+    }
+
+    /// FEATURE_GEOMETRY_RECORD_MULTIPOINT as `(easting, northing, depth)`
+    pub fn soundings(self, soundings: &[(f32, f32, f32)]) -> Self {
+        let mut payload = Self::geometry_extent();
+        payload.extend_from_slice(&(soundings.len() as u32).to_le_bytes());
+
+        for (easting, northing, depth) in soundings {
+            payload.extend_from_slice(&easting.to_le_bytes());
+            payload.extend_from_slice(&northing.to_le_bytes());
+            payload.extend_from_slice(&depth.to_le_bytes());
+        }
+
+        self.record(83, &payload)
+    }
+
+    /// FEATURE_GEOMETRY_RECORD_LINE, elements as
+    /// `[start node, edge, end node, direction]`
+    pub fn line(self, elements: &[[u32; 4]]) -> Self {
+        let mut payload = Self::geometry_extent();
+        payload.extend_from_slice(&(elements.len() as u32).to_le_bytes());
+        payload.extend(
+            elements
+                .iter()
+                .flatten()
+                .flat_map(|value| value.to_le_bytes()),
+        );
+        self.record(81, &payload)
+    }
+
+    /// FEATURE_GEOMETRY_RECORD_AREA without tessellation, elements as in `line`
+    pub fn area(self, elements: &[[u32; 4]]) -> Self {
+        let mut payload = Self::geometry_extent();
+        // contour, triangle primitive and edge vector counts
+        payload.extend_from_slice(&0u32.to_le_bytes());
+        payload.extend_from_slice(&0u32.to_le_bytes());
+        payload.extend_from_slice(&(elements.len() as u32).to_le_bytes());
+        payload.extend(
+            elements
+                .iter()
+                .flatten()
+                .flat_map(|value| value.to_le_bytes()),
+        );
+        self.record(82, &payload)
+    }
+
+    /// VECTOR_EDGE_NODE_TABLE_RECORD, points as projected (easting, northing)
+    pub fn edges(self, edges: &[(u32, &[(f32, f32)])]) -> Self {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(edges.len() as u32).to_le_bytes());
+
+        for (index, points) in edges {
+            payload.extend_from_slice(&index.to_le_bytes());
+            payload.extend_from_slice(&(points.len() as u32).to_le_bytes());
+            for (easting, northing) in points.iter() {
+                payload.extend_from_slice(&easting.to_le_bytes());
+                payload.extend_from_slice(&northing.to_le_bytes());
+            }
+        }
+
+        self.record(96, &payload)
+    }
+
+    /// VECTOR_CONNECTED_NODE_TABLE_RECORD as `(index, easting, northing)`
+    pub fn nodes(self, nodes: &[(u32, f32, f32)]) -> Self {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&(nodes.len() as u32).to_le_bytes());
+
+        for (index, easting, northing) in nodes {
+            payload.extend_from_slice(&index.to_le_bytes());
+            payload.extend_from_slice(&easting.to_le_bytes());
+            payload.extend_from_slice(&northing.to_le_bytes());
+        }
+
+        self.record(97, &payload)
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Per-feature extent of geometry records, the whole cell
+    fn geometry_extent() -> Vec<u8> {
+        [54.0, 55.0, 10.0, 11.0f64]
+            .into_iter()
+            .flat_map(f64::to_le_bytes)
+            .collect()
+    }
+}
+
+/// Bytes of a small decrypted SENC: version, cell name, extent and a single
+/// LNDMRK point feature at the cell center.
+#[allow(dead_code)]
+pub fn minimal_chart_bytes(name: &str) -> Vec<u8> {
+    ChartBuilder::new(name)
+        .feature(S57Type::LNDMRK, 1, 1)
+        .point(54.5, 10.5)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{chartfile::ChartFile, s57::Position};
+
+    #[test]
+    fn minimal_chart_parses() {
+        let bytes = minimal_chart_bytes("TEST0001");
+        let chart = ChartFile::parse_file(&mut Cursor::new(bytes)).unwrap();
+
+        assert_eq!(chart.name(), "TEST0001");
+        assert_eq!(
+            chart.extent().top_left,
+            Position {
+                lat: 55.0,
+                lon: 10.0
+            }
+        );
+        assert_eq!(
+            chart.extent().bottom_right,
+            Position {
+                lat: 54.0,
+                lon: 11.0
+            }
+        );
+
+        assert_eq!(chart.features().len(), 1);
+        let landmark = &chart.features()[0];
+        assert_eq!(landmark.s57_type(), S57Type::LNDMRK);
+        assert_eq!(landmark.feature_id(), 1);
+        assert_eq!(
+            landmark.point_geometry(),
+            Some(&Position {
+                lat: 54.5,
+                lon: 10.5
+            })
+        );
+    }
+}
@@ -16,29 +16,99 @@
  * language governing permissions and limitations under the Licence.
  */
 
-use crate::s57::Position;
+use crate::s57::{Position, Rect};
+
+/// Decoding of Osenc payloads, which are stored little-endian and without
+/// padding, independent of host byte order and struct layout
+pub trait FromLeBytes: Sized {
+    /// Encoded size in bytes, for variable length payloads the fixed part
+    const SIZE: usize;
+
+    /// Panics if `buf` is shorter than `SIZE`
+    fn from_le_bytes(buf: &[u8]) -> Self;
+}
+
+/// Reads little-endian fields one after another from a payload buffer
+pub struct LeBytes<'a> {
+    buf: &'a [u8],
+    offset: usize,
+}
+
+#[allow(dead_code)]
+impl<'a> LeBytes<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        LeBytes { buf, offset: 0 }
+    }
+
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&self.buf[self.offset..self.offset + N]);
+        self.offset += N;
+        bytes
+    }
+
+    pub fn u8(&mut self) -> u8 {
+        self.take::<1>()[0]
+    }
+
+    pub fn u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.take())
+    }
+
+    pub fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take())
+    }
+
+    pub fn i32(&mut self) -> i32 {
+        i32::from_le_bytes(self.take())
+    }
+
+    pub fn f32(&mut self) -> f32 {
+        f32::from_le_bytes(self.take())
+    }
+
+    pub fn f64(&mut self) -> f64 {
+        f64::from_le_bytes(self.take())
+    }
+
+    /// Everything not read yet
+    pub fn rest(&self) -> &'a [u8] {
+        &self.buf[self.offset..]
+    }
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
-#[repr(C, packed)]
 pub struct OsencRecordBase {
     record_type: u16,
     record_len: u32,
 }
 
+impl FromLeBytes for OsencRecordBase {
+    const SIZE: usize = 6;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencRecordBase {
+            record_type: bytes.u16(),
+            record_len: bytes.u32(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl OsencRecordBase {
     pub fn get_record_type(&self) -> u16 {
-        return self.record_type;
+        self.record_type
     }
     pub fn get_record_len(&self) -> u32 {
-        return self.record_len;
+        self.record_len
     }
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
-#[repr(C, packed)]
 pub struct OsencServerstatRecordPayload {
     server_status: u16,
     decrypt_status: u16,
@@ -48,6 +118,23 @@ pub struct OsencServerstatRecordPayload {
     grace_days_remaining: u16,
 }
 
+impl FromLeBytes for OsencServerstatRecordPayload {
+    const SIZE: usize = 12;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencServerstatRecordPayload {
+            server_status: bytes.u16(),
+            decrypt_status: bytes.u16(),
+            expire_status: bytes.u16(),
+            expire_days_remaining: bytes.u16(),
+            grace_days_allowed: bytes.u16(),
+            grace_days_remaining: bytes.u16(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl OsencServerstatRecordPayload {
     pub fn get_server_status(&self) -> u16 {
@@ -77,7 +164,6 @@ impl OsencServerstatRecordPayload {
 
 #[allow(dead_code)]
 #[derive(Debug)]
-#[repr(C, packed)]
 pub struct OsencExtentRecordPayload {
     pub extent_sw_lat: f64,
     pub extent_sw_lon: f64,
@@ -89,92 +175,162 @@ pub struct OsencExtentRecordPayload {
     pub extent_se_lon: f64,
 }
 
+impl FromLeBytes for OsencExtentRecordPayload {
+    const SIZE: usize = 64;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencExtentRecordPayload {
+            extent_sw_lat: bytes.f64(),
+            extent_sw_lon: bytes.f64(),
+            extent_nw_lat: bytes.f64(),
+            extent_nw_lon: bytes.f64(),
+            extent_ne_lat: bytes.f64(),
+            extent_ne_lon: bytes.f64(),
+            extent_se_lat: bytes.f64(),
+            extent_se_lon: bytes.f64(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
-#[repr(C, packed)]
 pub struct OsencFeatureIdentificationRecordPayload {
     feature_type_code: u16,
     feature_id: u16,
     feature_primitive: u8,
 }
 
+impl FromLeBytes for OsencFeatureIdentificationRecordPayload {
+    const SIZE: usize = 5;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencFeatureIdentificationRecordPayload {
+            feature_type_code: bytes.u16(),
+            feature_id: bytes.u16(),
+            feature_primitive: bytes.u8(),
+        }
+    }
+}
+
+#[allow(dead_code)]
 impl OsencFeatureIdentificationRecordPayload {
     pub fn get_feature_type_code(&self) -> u16 {
-        return self.feature_type_code;
+        self.feature_type_code
+    }
+    pub fn get_feature_id(&self) -> u16 {
+        self.feature_id
+    }
+    pub fn get_feature_primitive(&self) -> u8 {
+        self.feature_primitive
     }
 }
 
-#[repr(C)]
-#[repr(packed)]
 pub struct OsencAttributeRecordPayload {
     attribute_type_code: u16,
     attribute_value_type: u8,
     attribute_value: OsencAttributeValue,
 }
 
+impl FromLeBytes for OsencAttributeRecordPayload {
+    const SIZE: usize = 3;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencAttributeRecordPayload {
+            attribute_type_code: bytes.u16(),
+            attribute_value_type: bytes.u8(),
+            attribute_value: OsencAttributeValue {
+                bytes: bytes.rest().to_vec(),
+            },
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl OsencAttributeRecordPayload {
     pub fn get_attribute_type_code(&self) -> u16 {
-        return self.attribute_type_code;
+        self.attribute_type_code
     }
     pub fn get_attribute_value_type(&self) -> u8 {
-        return self.attribute_value_type;
+        self.attribute_value_type
     }
-    pub fn get_attribute_value(&self) -> OsencAttributeValue {
-        return self.attribute_value;
+    pub fn get_attribute_value(&self) -> &OsencAttributeValue {
+        &self.attribute_value
     }
 }
 
-#[repr(C)]
-#[repr(packed)]
-#[derive(Clone, Copy)]
-pub union OsencAttributeValue {
-    attribute_value_int: u32,
-    attribute_value_double: f64,
-    attribute_value_char_ptr: *const u8,
+/// Value bytes of an attribute record. Their length depends on the value
+/// type, so the numeric getters read missing trailing bytes as zero.
+#[derive(Debug, Clone)]
+pub struct OsencAttributeValue {
+    bytes: Vec<u8>,
 }
 
 #[allow(dead_code)]
 impl OsencAttributeValue {
+    fn padded<const N: usize>(&self) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        let len = self.bytes.len().min(N);
+        bytes[..len].copy_from_slice(&self.bytes[..len]);
+        bytes
+    }
+
     pub fn get_int(&self) -> u32 {
-        unsafe {
-            return self.attribute_value_int;
-        }
+        u32::from_le_bytes(self.padded())
     }
 
     pub fn get_double(&self) -> f64 {
-        unsafe {
-            return self.attribute_value_double;
-        }
+        f64::from_le_bytes(self.padded())
     }
 
-    pub fn get_char_ptr(&self) -> *const u8 {
-        unsafe {
-            return self.attribute_value_char_ptr;
-        }
+    /// NUL terminated string, `None` if it isn't valid UTF-8
+    pub fn get_str(&self) -> Option<&str> {
+        let end = self
+            .bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(self.bytes.len());
+
+        std::str::from_utf8(&self.bytes[..end]).ok()
     }
 }
 
 #[derive(Debug)]
-#[repr(C, packed)]
 #[allow(dead_code)]
 pub struct OsencPointGeometryRecordPayload {
     lat: f64,
     lon: f64,
 }
 
-impl Into<Position> for OsencPointGeometryRecordPayload {
-    fn into(self) -> Position {
-        return Position {
-            lat: self.lat,
-            lon: self.lon,
-        };
+impl FromLeBytes for OsencPointGeometryRecordPayload {
+    const SIZE: usize = 16;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencPointGeometryRecordPayload {
+            lat: bytes.f64(),
+            lon: bytes.f64(),
+        }
+    }
+}
+
+impl From<OsencPointGeometryRecordPayload> for Position {
+    fn from(val: OsencPointGeometryRecordPayload) -> Self {
+        Position {
+            lat: val.lat,
+            lon: val.lon,
+        }
     }
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Copy, Clone)]
-#[repr(C, packed)]
 pub struct OsencAreaGeometryRecordPayload {
     extent_s_lat: f64,
     extent_n_lat: f64,
@@ -185,6 +341,24 @@ pub struct OsencAreaGeometryRecordPayload {
     edgevector_count: u32,
 }
 
+impl FromLeBytes for OsencAreaGeometryRecordPayload {
+    const SIZE: usize = 44;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencAreaGeometryRecordPayload {
+            extent_s_lat: bytes.f64(),
+            extent_n_lat: bytes.f64(),
+            extent_w_lon: bytes.f64(),
+            extent_e_lon: bytes.f64(),
+            contour_count: bytes.u32(),
+            triprim_count: bytes.u32(),
+            edgevector_count: bytes.u32(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl OsencAreaGeometryRecordPayload {
     pub fn get_extent_s_lat(&self) -> f64 {
@@ -213,10 +387,23 @@ impl OsencAreaGeometryRecordPayload {
     pub fn get_edgevector_count(&self) -> u32 {
         self.edgevector_count
     }
+
+    /// Extent of the feature as declared by the record
+    pub fn get_extent(&self) -> Rect {
+        Rect {
+            top_left: Position {
+                lat: self.extent_n_lat,
+                lon: self.extent_w_lon,
+            },
+            bottom_right: Position {
+                lat: self.extent_s_lat,
+                lon: self.extent_e_lon,
+            },
+        }
+    }
 }
 
 #[derive(Debug)]
-#[repr(C, packed)]
 #[allow(dead_code)]
 pub struct OsencLineGeometryRecordPayload {
     extent_s_lat: f64,
@@ -226,8 +413,40 @@ pub struct OsencLineGeometryRecordPayload {
     edgevector_count: u32,
 }
 
+#[allow(dead_code)]
+impl OsencLineGeometryRecordPayload {
+    /// Extent of the feature as declared by the record
+    pub fn get_extent(&self) -> Rect {
+        Rect {
+            top_left: Position {
+                lat: self.extent_n_lat,
+                lon: self.extent_w_lon,
+            },
+            bottom_right: Position {
+                lat: self.extent_s_lat,
+                lon: self.extent_e_lon,
+            },
+        }
+    }
+}
+
+impl FromLeBytes for OsencLineGeometryRecordPayload {
+    const SIZE: usize = 36;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencLineGeometryRecordPayload {
+            extent_s_lat: bytes.f64(),
+            extent_n_lat: bytes.f64(),
+            extent_w_lon: bytes.f64(),
+            extent_e_lon: bytes.f64(),
+            edgevector_count: bytes.u32(),
+        }
+    }
+}
+
 #[derive(Debug)]
-#[repr(C, packed)]
 #[allow(dead_code)]
 pub struct OsencMultipointGeometryRecordPayload {
     extent_s_lat: f64,
@@ -236,3 +455,36 @@ pub struct OsencMultipointGeometryRecordPayload {
     extent_e_lon: f64,
     pub point_count: u32,
 }
+
+#[allow(dead_code)]
+impl OsencMultipointGeometryRecordPayload {
+    /// Extent of the feature as declared by the record
+    pub fn get_extent(&self) -> Rect {
+        Rect {
+            top_left: Position {
+                lat: self.extent_n_lat,
+                lon: self.extent_w_lon,
+            },
+            bottom_right: Position {
+                lat: self.extent_s_lat,
+                lon: self.extent_e_lon,
+            },
+        }
+    }
+}
+
+impl FromLeBytes for OsencMultipointGeometryRecordPayload {
+    const SIZE: usize = 36;
+
+    fn from_le_bytes(buf: &[u8]) -> Self {
+        let mut bytes = LeBytes::new(buf);
+
+        OsencMultipointGeometryRecordPayload {
+            extent_s_lat: bytes.f64(),
+            extent_n_lat: bytes.f64(),
+            extent_w_lon: bytes.f64(),
+            extent_e_lon: bytes.f64(),
+            point_count: bytes.u32(),
+        }
+    }
+}